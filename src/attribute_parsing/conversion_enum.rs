@@ -1,11 +1,68 @@
 use darling::{FromMeta, FromVariant};
-use syn::{DataEnum, Path, spanned::Spanned};
+use syn::{DataEnum, LitStr, Type, spanned::Spanned};
 
 use super::{
-    conversion_field::{ConvertibleField, extract_convertible_fields},
-    conversion_meta::ConversionMethod,
+    conversion_field::{
+        ConvertibleField, FieldIdentifier, extract_convertible_fields, other_side_named,
+    },
+    conversion_meta::{ConversionMethod, FieldMapping, Naming, PositionFill},
+    rename_rule::RenameRule,
 };
 
+/// Fields filled in when a unit variant converts into a variant with a payload, written as
+/// `default_fields(x, y = "expr")`, or `default_fields(0, 1 = "expr")` for the positions of a
+/// tuple variant. Listed fields without a value use `Default::default()`.
+#[derive(Clone, Default)]
+struct DefaultFields(Vec<(syn::Member, Option<syn::Expr>)>);
+
+impl FromMeta for DefaultFields {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    // Parsed by hand, since positions like `1 = "expr"` aren't valid nested meta items
+    fn from_meta(item: &syn::Meta) -> darling::Result<Self> {
+        let list = match item {
+            syn::Meta::Path(_) => return Self::from_word(),
+            syn::Meta::List(list) => list,
+            syn::Meta::NameValue(_) => {
+                return Err(darling::Error::unsupported_format("name value").with_span(item));
+            }
+        };
+        let entries = list.parse_args_with(
+            syn::punctuated::Punctuated::<DefaultField, syn::Token![,]>::parse_terminated,
+        )?;
+        Ok(Self(
+            entries
+                .into_iter()
+                .map(|DefaultField(member, value)| (member, value))
+                .collect(),
+        ))
+    }
+}
+
+// One entry of `default_fields`: a field name or position, with an optional value that is
+// either an expression or a string holding one
+struct DefaultField(syn::Member, Option<syn::Expr>);
+
+impl syn::parse::Parse for DefaultField {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let member = input.parse()?;
+        if !input.peek(syn::Token![=]) {
+            return Ok(Self(member, None));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let value = match input.parse()? {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) => value.parse()?,
+            value => value,
+        };
+        Ok(Self(member, Some(value)))
+    }
+}
+
 #[derive(FromMeta)]
 struct VariantConvAttrs {
     #[darling(default)]
@@ -13,16 +70,55 @@ struct VariantConvAttrs {
     // Add other variant-specific attributes here
     #[darling(default)]
     skip: bool,
+    #[darling(default)]
+    with_func: Option<syn::Path>,
+    #[darling(default)]
+    by_ref: bool,
+    #[darling(default)]
+    fields: Option<Vec<LitStr>>,
+    #[darling(default)]
+    discard_fields: bool,
+    #[darling(default)]
+    default_fields: Option<DefaultFields>,
+    #[darling(default)]
+    arity: Option<usize>,
+    #[darling(default, multiple)]
+    fill: Vec<PositionFill>,
 }
 
 #[derive(FromVariant)]
 #[darling(attributes(convert))]
 struct ConvertVariant {
     ident: syn::Ident,
+    discriminant: Option<syn::Expr>,
     #[darling(default)]
     rename: Option<String>,
+    // Integer value used by `repr` conversions, overriding the discriminant
+    #[darling(default)]
+    value: Option<syn::Expr>,
+    // Extra spellings accepted when parsing a `string` enum
+    #[darling(default, multiple)]
+    alias: Vec<String>,
     #[darling(default)]
     skip: bool,
+    #[darling(default)]
+    with_func: Option<syn::Path>,
+    #[darling(default)]
+    by_ref: bool,
+    // Names for the fields of a tuple variant, when the other variant has named fields
+    #[darling(default, rename = "fields")]
+    field_names: Option<Vec<LitStr>>,
+    // Drop the source payload, converting into a unit variant
+    #[darling(default)]
+    discard_fields: bool,
+    // Fill the target payload when converting from a unit variant
+    #[darling(default)]
+    default_fields: Option<DefaultFields>,
+    // Number of positions of the other tuple variant, filled up by `default`
+    #[darling(default)]
+    arity: Option<usize>,
+    #[darling(default, multiple)]
+    fill: Vec<PositionFill>,
 
     // Different conversion types for variants
     #[darling(default)]
@@ -33,22 +129,46 @@ struct ConvertVariant {
     into: Option<VariantConvAttrs>,
     #[darling(default)]
     try_into: Option<VariantConvAttrs>,
+    // Shared by the into and from conversions of `both`, or try_into and try_from
+    #[darling(default)]
+    both: Option<VariantConvAttrs>,
+    #[darling(default)]
+    try_both: Option<VariantConvAttrs>,
 }
 
 #[derive(Clone)]
 pub(crate) struct ConversionVariant {
     pub(crate) source_name: syn::Ident,
     pub(crate) target_name: syn::Ident,
-    pub(crate) named_variant: bool,
+    pub(crate) source_named: bool,
+    pub(crate) target_named: bool,
     pub(crate) fields: Vec<ConvertibleField>,
+    // Function building the whole target value from the variant payload
+    pub(crate) conversion_func: Option<syn::Path>,
+    // Wether the payload is passed to `conversion_func` by reference
+    pub(crate) func_by_ref: bool,
+    // Wether the source payload is dropped, converting into a unit variant
+    pub(crate) discard_fields: bool,
+    // Values for the target payload, when converting from a unit variant
+    pub(crate) filled_fields: Option<Vec<(FieldIdentifier, syn::Expr)>>,
+    // Number of positions of an unnamed target variant, filled up by `default`
+    pub(crate) arity: Option<usize>,
+    // Values for target positions that no field maps to
+    pub(crate) fill: Vec<PositionFill>,
 }
 
 pub(crate) fn extract_enum_variants(
     data_enum: &DataEnum,
     conversion_type: ConversionMethod,
-    other_type: &Path,
+    other_type: &Type,
+    naming: &Naming,
+    mapping: &FieldMapping,
 ) -> syn::Result<Vec<ConversionVariant>> {
     let is_from = conversion_type.is_from();
+    mapping.check_names(
+        data_enum.variants.iter().map(|variant| &variant.ident),
+        "variant",
+    )?;
     data_enum
         .variants
         .iter()
@@ -64,31 +184,134 @@ pub(crate) fn extract_enum_variants(
                 }
             };
 
-            let named_variant = matches!(variant.fields, syn::Fields::Named(_));
+            let derived_named = matches!(variant.fields, syn::Fields::Named(_));
 
             // Get the specific conversion attributes based on conversion type
             let variant_conv_attrs = match conversion_type {
-                ConversionMethod::From => convert_variant.from,
-                ConversionMethod::TryFrom => convert_variant.try_from,
-                ConversionMethod::Into => convert_variant.into,
-                ConversionMethod::TryInto => convert_variant.try_into,
+                ConversionMethod::From => convert_variant.from.or(convert_variant.both),
+                ConversionMethod::TryFrom => convert_variant.try_from.or(convert_variant.try_both),
+                ConversionMethod::Into => convert_variant.into.or(convert_variant.both),
+                ConversionMethod::TryInto => convert_variant.try_into.or(convert_variant.try_both),
             };
 
             // Skip if marked with skip
-            if convert_variant.skip || variant_conv_attrs.as_ref().is_some_and(|attr| attr.skip) {
+            if convert_variant.skip
+                || variant_conv_attrs.as_ref().is_some_and(|attr| attr.skip)
+                || mapping.skips(&convert_variant.ident)
+            {
                 return Ok(None); // Return None to filter out later
             }
 
             // Determine the target variant name with priority:
             // 1. Conversion-specific rename
-            // 2. Top-level rename
-            // 3. Original variant name
+            // 2. The conversion's `map`
+            // 3. Top-level rename
+            // 4. Original variant name, following the conversion's naming rules
             let other_variant_name = variant_conv_attrs
                 .as_ref()
                 .and_then(|attrs| attrs.rename.as_ref())
-                .or(convert_variant.rename.as_ref())
                 .map(|rename| syn::Ident::new(rename, variant.span()))
-                .unwrap_or_else(|| convert_variant.ident.clone());
+                .or_else(|| mapping.renamed(&convert_variant.ident))
+                .or_else(|| {
+                    let rename = convert_variant.rename.as_ref()?;
+                    Some(syn::Ident::new(rename, variant.span()))
+                })
+                .map(Ok)
+                .unwrap_or_else(|| naming.variant_name(&convert_variant.ident, &variant.attrs))?;
+
+            let conversion_func = variant_conv_attrs
+                .as_ref()
+                .and_then(|attrs| attrs.with_func.as_ref())
+                .or(convert_variant.with_func.as_ref())
+                .cloned();
+
+            let func_by_ref = convert_variant.by_ref
+                || variant_conv_attrs
+                    .as_ref()
+                    .is_some_and(|attrs| attrs.by_ref);
+
+            // Naming rules apply to the variants, and not to the fields inside them, apart
+            // from the serde names those fields have of their own
+            let field_naming = Naming {
+                use_serde_names: naming.use_serde_names,
+                ..Naming::default()
+            };
+            let mut fields = extract_convertible_fields(
+                &variant.fields,
+                conversion_type,
+                other_type,
+                &field_naming,
+                &FieldMapping::default(),
+            )?;
+            if fields.iter().any(|field| field.flatten.is_some()) {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "flatten is not supported on enum variant fields",
+                ));
+            }
+            if fields.iter().any(|field| field.getter.is_some()) {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "`getter` is not supported on enum variant fields",
+                ));
+            }
+
+            let field_names = variant_conv_attrs
+                .as_ref()
+                .and_then(|attrs| attrs.fields.as_ref())
+                .or(convert_variant.field_names.as_ref());
+            if let Some(field_names) = field_names {
+                name_positional_fields(variant, &mut fields, field_names, is_from)?;
+            }
+
+            let discard_fields = convert_variant.discard_fields
+                || variant_conv_attrs
+                    .as_ref()
+                    .is_some_and(|attrs| attrs.discard_fields);
+            if discard_fields && is_from && !variant.fields.is_empty() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "`discard_fields` in from conversions requires a unit variant",
+                ));
+            }
+
+            let default_fields = variant_conv_attrs
+                .as_ref()
+                .and_then(|attrs| attrs.default_fields.as_ref())
+                .or(convert_variant.default_fields.as_ref());
+            let filled_fields = default_fields
+                .map(|default_fields| fill_fields(variant, default_fields, is_from))
+                .transpose()?;
+
+            // In from conversions the target is this variant, so its arity is known
+            let arity = variant_conv_attrs
+                .as_ref()
+                .and_then(|attrs| attrs.arity)
+                .or(convert_variant.arity);
+            if is_from && arity.is_some() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "`arity` only applies to into conversions",
+                ));
+            }
+            let arity = if is_from {
+                Some(variant.fields.len())
+            } else {
+                arity
+            };
+            let fill = match variant_conv_attrs.as_ref() {
+                Some(attrs) if !attrs.fill.is_empty() => attrs.fill.clone(),
+                _ => convert_variant.fill.clone(),
+            };
+
+            let mut other_named =
+                other_side_named(&mut fields, is_from, derived_named, variant.span())?;
+            // A unit variant of ours becomes whatever shape the listed fields give it
+            if !is_from && let Some(filled_fields) = &filled_fields {
+                other_named = filled_fields
+                    .iter()
+                    .all(|(name, _)| matches!(name, FieldIdentifier::Named(_)));
+            }
 
             let (source_name, target_name) = if is_from {
                 (other_variant_name, convert_variant.ident.clone())
@@ -96,13 +319,257 @@ pub(crate) fn extract_enum_variants(
                 (convert_variant.ident.clone(), other_variant_name)
             };
 
+            let (source_named, target_named) = if is_from {
+                (other_named, derived_named)
+            } else {
+                (derived_named, other_named)
+            };
+
             Ok(Some(ConversionVariant {
                 source_name,
                 target_name,
-                named_variant,
-                fields: extract_convertible_fields(&variant.fields, conversion_type, other_type)?,
+                source_named,
+                target_named,
+                fields,
+                conversion_func,
+                func_by_ref,
+                discard_fields,
+                filled_fields,
+                arity,
+                fill,
             }))
         })
         .filter_map(|result| result.transpose())
         .collect()
 }
+
+/// The integer value of every variant of a fieldless enum, for `repr` conversions. Values come
+/// from `#[convert(value = ...)]`, then explicit discriminants, and otherwise follow the
+/// previous variant like implicit discriminants do.
+pub(crate) fn extract_variant_values(
+    data_enum: &DataEnum,
+) -> syn::Result<Vec<(syn::Ident, syn::Expr)>> {
+    let mut next_value: syn::Expr = syn::parse_quote!(0);
+    data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            if !variant.fields.is_empty() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "`repr` conversions require an enum without fields",
+                ));
+            }
+
+            let convert_variant = ConvertVariant::from_variant(variant).map_err(|e| {
+                syn::Error::new(
+                    variant.span(),
+                    format!("Failed to parse variant attributes: {}", e),
+                )
+            })?;
+
+            let value = convert_variant
+                .value
+                .or(convert_variant.discriminant)
+                .unwrap_or(next_value.clone());
+            next_value = syn::parse_quote!((#value) + 1);
+
+            Ok((convert_variant.ident, value))
+        })
+        .collect()
+}
+
+/// A variant of a fieldless enum with its `string` spelling and any aliases
+pub(crate) struct VariantSpelling {
+    pub(crate) ident: syn::Ident,
+    pub(crate) name: String,
+    pub(crate) aliases: Vec<String>,
+}
+
+/// The spelling of every variant of a fieldless enum, for `string` conversions. A `rename`
+/// takes precedence over the case convention, which applies to the variant's identifier.
+pub(crate) fn extract_variant_spellings(
+    data_enum: &DataEnum,
+    case: Option<RenameRule>,
+) -> syn::Result<Vec<VariantSpelling>> {
+    data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            if !variant.fields.is_empty() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "`string` conversions require an enum without fields",
+                ));
+            }
+
+            let convert_variant = ConvertVariant::from_variant(variant).map_err(|e| {
+                syn::Error::new(
+                    variant.span(),
+                    format!("Failed to parse variant attributes: {}", e),
+                )
+            })?;
+
+            let ident_name = convert_variant.ident.to_string();
+            let name = convert_variant.rename.unwrap_or_else(|| match case {
+                Some(case) => case.apply(&ident_name),
+                None => ident_name,
+            });
+
+            Ok(VariantSpelling {
+                ident: convert_variant.ident,
+                name,
+                aliases: convert_variant.alias,
+            })
+        })
+        .collect()
+}
+
+// Gives the other variant's fields the names listed in `fields = [...]`, for fields that
+// weren't mapped to a name or index of their own
+fn name_positional_fields(
+    variant: &syn::Variant,
+    fields: &mut [ConvertibleField],
+    field_names: &[LitStr],
+    is_from: bool,
+) -> syn::Result<()> {
+    if !matches!(variant.fields, syn::Fields::Unnamed(_)) {
+        return Err(syn::Error::new(
+            variant.span(),
+            "`fields` can only be used on tuple variants",
+        ));
+    }
+    if field_names.len() != variant.fields.len() {
+        return Err(syn::Error::new(
+            variant.span(),
+            format!(
+                "Expected {} names in `fields`, found {}",
+                variant.fields.len(),
+                field_names.len()
+            ),
+        ));
+    }
+
+    let field_names = field_names
+        .iter()
+        .map(|name| {
+            name.parse::<syn::Ident>().map_err(|_| {
+                syn::Error::new(
+                    name.span(),
+                    format!("`{}` in `fields` is not an identifier", name.value()),
+                )
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    for field in fields.iter_mut() {
+        let derived_index = if is_from {
+            field.target_name.index()
+        } else {
+            field.source_name.index()
+        };
+        let other_name = field.other_name_mut(is_from);
+        if let Some(index) = derived_index
+            && other_name.index() == Some(index)
+        {
+            *other_name = FieldIdentifier::Named(field_names[index].clone());
+        }
+    }
+
+    Ok(())
+}
+
+// Resolves `default_fields` into a value for every field of the target variant. In into
+// conversions the unit variant is ours and the fields are the listed ones, named or
+// positional, while in from conversions every field of our variant gets a value.
+fn fill_fields(
+    variant: &syn::Variant,
+    DefaultFields(listed): &DefaultFields,
+    is_from: bool,
+) -> syn::Result<Vec<(FieldIdentifier, syn::Expr)>> {
+    let value_or_default = |value: Option<&syn::Expr>| {
+        value
+            .cloned()
+            .unwrap_or_else(|| syn::parse_quote!(Default::default()))
+    };
+
+    if !is_from {
+        if !variant.fields.is_empty() {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`default_fields` in into conversions requires a unit variant",
+            ));
+        }
+        let positional = listed
+            .iter()
+            .any(|(member, _)| matches!(member, syn::Member::Unnamed(_)));
+        let mut filled = Vec::new();
+        for (i, (member, value)) in listed.iter().enumerate() {
+            let name = match member {
+                syn::Member::Named(ident) if !positional => FieldIdentifier::Named(ident.clone()),
+                syn::Member::Unnamed(index) if index.index as usize == i => {
+                    FieldIdentifier::Unnamed(i)
+                }
+                syn::Member::Unnamed(_) => {
+                    return Err(syn::Error::new(
+                        member.span(),
+                        format!("Expected position {} in `default_fields`", i),
+                    ));
+                }
+                syn::Member::Named(_) => {
+                    return Err(syn::Error::new(
+                        member.span(),
+                        "`default_fields` can't mix field names and positions",
+                    ));
+                }
+            };
+            filled.push((name, value_or_default(value.as_ref())));
+        }
+        return Ok(filled);
+    }
+
+    if variant.fields.is_empty() {
+        return Err(syn::Error::new(
+            variant.span(),
+            "`default_fields` in from conversions requires a variant with fields",
+        ));
+    }
+    // The member a field of our variant is listed under
+    let member_of = |i: usize, field: &syn::Field| match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(i.into()),
+    };
+    if let Some((unknown, _)) = listed.iter().find(|(member, _)| {
+        !variant
+            .fields
+            .iter()
+            .enumerate()
+            .any(|(i, field)| member_of(i, field) == *member)
+    }) {
+        let unknown_name = match unknown {
+            syn::Member::Named(ident) => ident.to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        };
+        return Err(syn::Error::new(
+            unknown.span(),
+            format!("Variant {} has no field {}", variant.ident, unknown_name),
+        ));
+    }
+
+    Ok(variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = listed
+                .iter()
+                .find(|(member, _)| member_of(i, field) == *member)
+                .and_then(|(_, value)| value.as_ref());
+            let name = match &field.ident {
+                Some(ident) => FieldIdentifier::Named(ident.clone()),
+                None => FieldIdentifier::Unnamed(i),
+            };
+            (name, value_or_default(value))
+        })
+        .collect())
+}
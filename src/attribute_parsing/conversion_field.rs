@@ -1,11 +1,68 @@
-use darling::{FromField, FromMeta};
+use darling::{FromField, FromMeta, util::SpannedValue};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{ToTokens, format_ident, quote};
-use syn::{Field, Ident, Path, spanned::Spanned};
+use syn::{Field, Ident, Path, Type, spanned::Spanned};
 
 use crate::util::is_surrounding_type;
 
-use super::conversion_meta::ConversionMethod;
+use super::conversion_meta::{ConversionMethod, FieldMapping, Naming, path_to_type};
+
+/// Options of `flatten`, written as `flatten(fields(name, email = "user_email"))`
+#[derive(FromMeta, Debug, Clone)]
+struct FlattenAttrs {
+    fields: FlattenFields,
+}
+
+/// The nested fields that a flattened field spreads into, each with its name on the flat side
+#[derive(Debug, Clone)]
+struct FlattenFields(Vec<(Ident, Ident)>);
+
+impl FromMeta for FlattenFields {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                darling::ast::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                    let ident = path.require_ident()?;
+                    Ok((ident.clone(), ident.clone()))
+                }
+                darling::ast::NestedMeta::Meta(meta @ syn::Meta::NameValue(name_value)) => Ok((
+                    name_value.path.require_ident()?.clone(),
+                    Ident::from_meta(meta)?,
+                )),
+                _ => Err(darling::Error::unsupported_format("literal").with_span(item)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
+}
+
+/// A path to a nested member of the source, written as `source = "address?.city"`. Members
+/// followed by `?` are `Option`s that get unwrapped on the way.
+#[derive(Debug, Clone)]
+pub(crate) struct SourcePath(pub(crate) Vec<(FieldIdentifier, bool)>);
+
+impl FromMeta for SourcePath {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        value
+            .split('.')
+            .map(|segment| {
+                let (member, optional) = match segment.strip_suffix('?') {
+                    Some(member) => (member, true),
+                    None => (segment, false),
+                };
+                let member = match member.parse::<usize>() {
+                    Ok(index) => FieldIdentifier::Unnamed(index),
+                    Err(_) => FieldIdentifier::Named(syn::parse_str(member).map_err(|_| {
+                        darling::Error::custom(format!("Invalid source path `{}`", value))
+                    })?),
+                };
+                Ok((member, optional))
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
+}
 
 // Field level attributes using darling
 #[derive(FromMeta, Debug)]
@@ -21,15 +78,48 @@ struct ConvertFieldAttr {
     #[darling(default)]
     unwrap_or_default: bool,
 
+    #[darling(default)]
+    enum_i32: bool,
+
+    #[darling(default)]
+    some: bool,
+
     #[darling(default)]
     default: bool,
 
     // Add any other field attributes you need
     #[darling(default)]
-    rename: Option<String>,
+    rename: Option<SpannedValue<String>>,
+
+    #[darling(default)]
+    name: Option<SpannedValue<String>>,
+
+    #[darling(default)]
+    index: Option<usize>,
 
     #[darling(default)]
     with_func: Option<syn::Path>,
+
+    #[darling(default)]
+    flatten: Option<FlattenAttrs>,
+
+    #[darling(default)]
+    source: Option<SourcePath>,
+
+    #[darling(default)]
+    getter: Option<Ident>,
+
+    #[darling(default)]
+    borrow: bool,
+
+    #[darling(default)]
+    clone: bool,
+
+    #[darling(default)]
+    as_ref: bool,
+
+    #[darling(default)]
+    as_deref: bool,
 }
 
 #[derive(FromField, Debug)]
@@ -41,7 +131,13 @@ struct ConvertField {
     skip: bool,
 
     #[darling(default)]
-    rename: Option<String>,
+    rename: Option<SpannedValue<String>>,
+
+    #[darling(default)]
+    name: Option<SpannedValue<String>>,
+
+    #[darling(default)]
+    index: Option<usize>,
 
     #[darling(default)]
     default: bool,
@@ -52,9 +148,40 @@ struct ConvertField {
     #[darling(default)]
     unwrap_or_default: bool,
 
+    #[darling(default)]
+    enum_i32: bool,
+
+    #[darling(default)]
+    some: bool,
+
     #[darling(default)]
     with_func: Option<syn::Path>,
 
+    #[darling(default)]
+    flatten: Option<FlattenAttrs>,
+
+    #[darling(default)]
+    source: Option<SourcePath>,
+
+    // Method the field is read through in from conversions, for sources with private fields
+    #[darling(default)]
+    getter: Option<Ident>,
+
+    // Convert from a reference to the field instead of the field itself, which by_ref
+    // conversions would clone. `clone` overrides a top-level `borrow`.
+    #[darling(default)]
+    borrow: bool,
+
+    #[darling(default)]
+    clone: bool,
+
+    // Borrow the field with `as_ref()` or `as_deref()` instead of converting it
+    #[darling(default)]
+    as_ref: bool,
+
+    #[darling(default)]
+    as_deref: bool,
+
     // Different conversion types
     #[darling(default, multiple)]
     from: Vec<ConvertFieldAttr>,
@@ -67,6 +194,13 @@ struct ConvertField {
 
     #[darling(default, multiple)]
     try_into: Vec<ConvertFieldAttr>,
+
+    // Shared by the into and from conversions of `both`, or try_into and try_from
+    #[darling(default, multiple)]
+    both: Vec<ConvertFieldAttr>,
+
+    #[darling(default, multiple)]
+    try_both: Vec<ConvertFieldAttr>,
 }
 
 #[derive(Clone)]
@@ -78,9 +212,23 @@ pub(crate) enum FieldConversionMethod {
     Option,
     Iterator,
     HashMap,
+    IntToEnum,
+    // Borrows the field through `as_ref()`, for views of a borrowed source
+    AsRef,
+    // Borrows the field through `as_deref()`, as with `Option<String>` to `Option<&str>`
+    AsDeref,
 }
 
+/// How a `flatten` field maps to the flat type, as (nested field, flat field) pairs
 #[derive(Clone)]
+pub(crate) enum Flatten {
+    /// The nested fields become fields of the target
+    Spread(Vec<(Ident, Ident)>),
+    /// Fields of the source are grouped back into the nested type
+    Regroup(Box<syn::Type>, Vec<(Ident, Ident)>),
+}
+
+#[derive(Clone, Debug)]
 pub(crate) enum FieldIdentifier {
     Named(Ident),
     Unnamed(usize),
@@ -89,20 +237,35 @@ pub(crate) enum FieldIdentifier {
 #[derive(Clone)]
 pub(crate) struct ConvertibleField {
     pub(crate) source_name: FieldIdentifier,
+    // The type of the field on the derived side
+    pub(crate) ty: Type,
     pub(crate) span: Span,
     pub(crate) skip: bool,
     pub(crate) default: bool,
     pub(crate) method: FieldConversionMethod,
     pub(crate) target_name: FieldIdentifier,
     pub(crate) conversion_func: Option<syn::Path>,
+    pub(crate) flatten: Option<Flatten>,
+    // Nested members read after `source_name`, with wether each one is unwrapped
+    pub(crate) source_path: Vec<(FieldIdentifier, bool)>,
+    // Method the source field is read through instead of a field access
+    pub(crate) getter: Option<Ident>,
+    // Wether the field converts from a reference instead of an owned value
+    pub(crate) borrow: bool,
 }
 
 pub(crate) fn extract_convertible_fields(
     fields: &syn::Fields,
     conversion_type: ConversionMethod,
-    other_type: &Path,
+    other_type: &Type,
+    naming: &Naming,
+    mapping: &FieldMapping,
 ) -> syn::Result<Vec<ConvertibleField>> {
     let mut result = Vec::new();
+    mapping.check_names(
+        fields.iter().filter_map(|field| field.ident.as_ref()),
+        "field",
+    )?;
 
     // Determine which nested field we should check based on conversion type
     let is_from = matches!(
@@ -128,16 +291,24 @@ pub(crate) fn extract_convertible_fields(
             None => FieldIdentifier::Unnamed(i),
         };
 
-        // Get the specific conversion attributes based on conversion type
-        let field_conv_attrs: Vec<_> = match conversion_type {
-            ConversionMethod::From => convert_field.from,
-            ConversionMethod::TryFrom => convert_field.try_from,
-            ConversionMethod::Into => convert_field.into,
-            ConversionMethod::TryInto => convert_field.try_into,
+        // Get the specific conversion attributes based on conversion type, falling back to
+        // the ones shared by both directions
+        let (direction_attrs, both_attrs) = match conversion_type {
+            ConversionMethod::From => (convert_field.from, convert_field.both),
+            ConversionMethod::TryFrom => (convert_field.try_from, convert_field.try_both),
+            ConversionMethod::Into => (convert_field.into, convert_field.both),
+            ConversionMethod::TryInto => (convert_field.try_into, convert_field.try_both),
+        };
+        let applies = |attrs: &ConvertFieldAttr| {
+            !attrs
+                .path
+                .as_ref()
+                .is_some_and(|path| path_to_type(path.clone()) != *other_type)
+        };
+        let mut field_conv_attrs: Vec<_> = direction_attrs.into_iter().filter(applies).collect();
+        if field_conv_attrs.is_empty() {
+            field_conv_attrs = both_attrs.into_iter().filter(applies).collect();
         }
-        .into_iter()
-        .filter(|attrs| !attrs.path.as_ref().is_some_and(|path| path != other_type))
-        .collect();
 
         let field_conv_attrs = match field_conv_attrs.len() {
             0 | 1 => field_conv_attrs.first(),
@@ -162,12 +333,25 @@ pub(crate) fn extract_convertible_fields(
                 attrs.unwrap_or_default
             });
 
+        let enum_i32 = field_conv_attrs
+            .as_ref()
+            .map_or(convert_field.enum_i32, |attrs| attrs.enum_i32);
+
+        let some = field_conv_attrs
+            .as_ref()
+            .map_or(convert_field.some, |attrs| attrs.some);
+
         let default = field_conv_attrs
             .as_ref()
             .map_or(convert_field.default, |attrs| attrs.default);
 
         // Skip applies if either top-level or field-specific skip is true
-        let skip = convert_field.skip || field_conv_attrs.as_ref().is_some_and(|attrs| attrs.skip);
+        let skip = convert_field.skip
+            || field_conv_attrs.as_ref().is_some_and(|attrs| attrs.skip)
+            || field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| mapping.skips(ident));
 
         // Skip if marked with skip
         if skip {
@@ -175,18 +359,69 @@ pub(crate) fn extract_convertible_fields(
         }
 
         // Determine target field identifier with priority:
-        // 1. Field-specific rename
-        // 2. Top-level rename
-        // 3. Original field name
+        // 1. Field-specific rename, name or index
+        // 2. The conversion's `map`
+        // 3. Top-level rename, name or index
+        // 4. Original field name, following the conversion's naming rules
         let target_name = field_conv_attrs
             .as_ref()
-            .and_then(|attrs| attrs.rename.as_ref())
-            .or(convert_field.rename.as_ref())
-            .map(|rename| FieldIdentifier::Named(Ident::new(rename, field.span())))
-            .unwrap_or_else(|| source_name.clone());
-
-        // Determine field conversion method
-        let method = decide_field_method(field, is_from, unwrap, unwrap_or_default)?;
+            .and_then(|attrs| {
+                other_field_identifier(
+                    attrs.rename.as_ref().or(attrs.name.as_ref()),
+                    attrs.index,
+                    field,
+                )
+            })
+            .or_else(|| {
+                let ident = field.ident.as_ref()?;
+                mapping.renamed(ident).map(FieldIdentifier::Named).map(Ok)
+            })
+            .or_else(|| {
+                other_field_identifier(
+                    convert_field
+                        .rename
+                        .as_ref()
+                        .or(convert_field.name.as_ref()),
+                    convert_field.index,
+                    field,
+                )
+            })
+            .unwrap_or_else(|| match &source_name {
+                FieldIdentifier::Named(ident) => naming
+                    .field_name(ident, &field.attrs)
+                    .map(FieldIdentifier::Named),
+                FieldIdentifier::Unnamed(index) => Ok(FieldIdentifier::Unnamed(*index)),
+            })?;
+
+        // Determine field conversion method. A borrowed field converts as a whole, through
+        // `From<&T>`.
+        let borrow = match field_conv_attrs.as_ref() {
+            Some(attrs) if attrs.borrow || attrs.clone => attrs.borrow,
+            _ => convert_field.borrow && !convert_field.clone,
+        };
+        let as_ref = field_conv_attrs
+            .as_ref()
+            .map_or(convert_field.as_ref, |attrs| attrs.as_ref);
+        let as_deref = field_conv_attrs
+            .as_ref()
+            .map_or(convert_field.as_deref, |attrs| attrs.as_deref);
+        let method = if as_ref {
+            FieldConversionMethod::AsRef
+        } else if as_deref {
+            FieldConversionMethod::AsDeref
+        } else if borrow {
+            FieldConversionMethod::Plain
+        } else {
+            decide_field_method(field, is_from, unwrap, unwrap_or_default, enum_i32, some)?
+        };
+        if matches!(method, FieldConversionMethod::IntToEnum) && !conversion_type.is_falliable() {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "Converting an i32 to an enum can fail, use `try_into` or `try_from`",
+            ));
+        }
+        // `as_ref()` and `as_deref()` borrow the field where it is, without cloning it
+        let borrow = borrow || as_ref || as_deref;
 
         let conversion_func = field_conv_attrs
             .as_ref()
@@ -194,35 +429,149 @@ pub(crate) fn extract_convertible_fields(
             .or(convert_field.with_func.as_ref())
             .cloned();
 
+        let flatten = field_conv_attrs
+            .as_ref()
+            .and_then(|attrs| attrs.flatten.as_ref())
+            .or(convert_field.flatten.as_ref())
+            .map(|FlattenAttrs { fields }| {
+                if convert_field.ident.is_none() {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "flatten can only be used on named fields",
+                    ));
+                }
+                Ok(if is_from {
+                    Flatten::Regroup(Box::new(field.ty.clone()), fields.0.clone())
+                } else {
+                    Flatten::Spread(fields.0.clone())
+                })
+            })
+            .transpose()?;
+
         let (source_name, target_name) = if is_from {
             (target_name.clone(), source_name.clone())
         } else {
             (source_name.clone(), target_name.clone())
         };
 
+        // A source path replaces the source field, with its first member as the field
+        let (source_name, source_path) = match field_conv_attrs
+            .as_ref()
+            .and_then(|attrs| attrs.source.as_ref())
+            .or(convert_field.source.as_ref())
+        {
+            Some(SourcePath(path)) => (path[0].0.clone(), path.clone()),
+            None => (source_name, Vec::new()),
+        };
+
+        // Getters read the other side, so they only apply when converting from it
+        let scoped_getter = field_conv_attrs
+            .as_ref()
+            .and_then(|attrs| attrs.getter.clone());
+        if !is_from && let Some(getter) = scoped_getter.as_ref() {
+            return Err(syn::Error::new(
+                getter.span(),
+                "`getter` only applies to from conversions",
+            ));
+        }
+        let getter = if is_from {
+            scoped_getter.or(convert_field.getter.clone())
+        } else {
+            None
+        };
+
         result.push(ConvertibleField {
             source_name,
+            ty: field.ty.clone(),
             span: field.span(),
             skip: false, // We've already filtered out skipped fields
             method,
             target_name,
             default,
             conversion_func,
+            flatten,
+            source_path,
+            getter,
+            borrow,
         });
     }
 
-    // sort so that fields with conversion functions are first
-    result.sort_by(|a, b| {
-        if a.conversion_func.is_some() && b.conversion_func.is_none() {
-            std::cmp::Ordering::Less
-        } else if a.conversion_func.is_none() && b.conversion_func.is_some() {
-            std::cmp::Ordering::Greater
+    Ok(result)
+}
+
+impl ConvertibleField {
+    // The identifier of this field on the non-derived side of the conversion
+    pub(crate) fn other_name_mut(&mut self, is_from: bool) -> &mut FieldIdentifier {
+        if is_from {
+            &mut self.source_name
         } else {
-            std::cmp::Ordering::Equal
+            &mut self.target_name
         }
-    });
+    }
+}
 
-    Ok(result)
+/// Decides whether the non-derived side of a conversion uses named fields, based on the
+/// identifiers the fields were mapped to. Falls back to the derived side's shape when no
+/// field says otherwise.
+pub(crate) fn other_side_named(
+    fields: &mut [ConvertibleField],
+    is_from: bool,
+    derived_named: bool,
+    span: Span,
+) -> syn::Result<bool> {
+    let mut named = fields
+        .iter_mut()
+        .map(|field| matches!(field.other_name_mut(is_from), FieldIdentifier::Named(_)));
+    let Some(first) = named.next() else {
+        return Ok(derived_named);
+    };
+    if named.any(|other| other != first) {
+        return Err(syn::Error::new(
+            span,
+            "Cannot mix named and positional fields in the same conversion",
+        ));
+    }
+
+    if !first {
+        let mut indexes: Vec<_> = fields
+            .iter_mut()
+            .filter_map(|field| field.other_name_mut(is_from).index())
+            .collect();
+        indexes.sort_unstable();
+        if indexes.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(syn::Error::new(
+                span,
+                "Multiple fields are mapped to the same index",
+            ));
+        }
+    }
+
+    Ok(first)
+}
+
+// The other side's field from a `rename` or `name`, which must be an identifier, or from
+// an `index`
+fn other_field_identifier(
+    name: Option<&SpannedValue<String>>,
+    index: Option<usize>,
+    field: &Field,
+) -> Option<syn::Result<FieldIdentifier>> {
+    let Some(name) = name else {
+        return index.map(|index| Ok(FieldIdentifier::Unnamed(index)));
+    };
+    Some(
+        syn::parse_str::<Ident>(name)
+            .map(|mut ident| {
+                ident.set_span(field.span());
+                FieldIdentifier::Named(ident)
+            })
+            .map_err(|_| {
+                syn::Error::new(
+                    name.span(),
+                    format!("`{}` is not an identifier", name.as_str()),
+                )
+            }),
+    )
 }
 
 pub(crate) fn decide_field_method(
@@ -230,6 +579,8 @@ pub(crate) fn decide_field_method(
     is_from: bool,
     unwrap: bool,
     unwrap_or_default: bool,
+    enum_i32: bool,
+    some: bool,
 ) -> syn::Result<FieldConversionMethod> {
     let is_option = is_surrounding_type(&field.ty, "Option");
     let is_vec = is_surrounding_type(&field.ty, "Vec");
@@ -242,6 +593,37 @@ pub(crate) fn decide_field_method(
         ));
     }
 
+    if enum_i32 {
+        if is_option || is_vec || is_hash_map || unwrap || unwrap_or_default {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "enum_i32 can only be used on plain i32 or enum fields",
+            ));
+        }
+        // Only the i32 -> enum direction can fail
+        let is_i32 = is_surrounding_type(&field.ty, "i32");
+        return Ok(if is_i32 != is_from {
+            FieldConversionMethod::IntToEnum
+        } else {
+            FieldConversionMethod::Plain
+        });
+    }
+
+    // The mirror image of `unwrap`: the other side holds this field in an `Option`
+    if some {
+        if is_option || unwrap || unwrap_or_default {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "some can only be used on non-Option fields, without unwrap",
+            ));
+        }
+        return Ok(if is_from {
+            FieldConversionMethod::UnwrapOption
+        } else {
+            FieldConversionMethod::SomeOption
+        });
+    }
+
     let unwrap_option = unwrap
         .then_some(FieldConversionMethod::UnwrapOption)
         .or(unwrap_or_default.then_some(FieldConversionMethod::UnwrapOrDefault));
@@ -295,6 +677,13 @@ impl ToTokens for FieldIdentifier {
 }
 
 impl FieldIdentifier {
+    pub(crate) fn index(&self) -> Option<usize> {
+        match self {
+            FieldIdentifier::Named(_) => None,
+            FieldIdentifier::Unnamed(index) => Some(*index),
+        }
+    }
+
     pub(crate) fn as_named(&self) -> TokenStream2 {
         match self {
             FieldIdentifier::Named(ident) => quote! { #ident },
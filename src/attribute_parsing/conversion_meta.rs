@@ -1,17 +1,358 @@
-use darling::{FromDeriveInput, FromMeta};
-use syn::{DeriveInput, Path};
+use darling::{
+    FromDeriveInput, FromMeta,
+    util::{Override, PathList},
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, Path, Type, ext::IdentExt};
+
+use super::{
+    rename_rule::RenameRule,
+    serde_names::{apply_to_field, apply_to_variant, serde_rename, serde_rename_all},
+};
 
 #[derive(Clone, Debug)]
 pub(crate) struct ConversionMeta {
-    pub(crate) source_name: Path,
-    pub(crate) target_name: Path,
+    pub(crate) source_name: Type,
+    pub(crate) target_name: Type,
     pub(crate) method: ConversionMethod,
+    // Wether the other side is a named type, or a tuple or array made of our fields
+    pub(crate) other_kind: OtherKind,
     // Wether we add ..Default::default() to conversions
     pub(crate) default_allowed: bool,
+    // Number of positions of an unnamed target, filled up by `default`
+    pub(crate) arity: Option<usize>,
+    // Values for target positions that no field maps to
+    pub(crate) fill: Vec<PositionFill>,
+    // Field of the struct on the other side that names the enum variant
+    pub(crate) tag: Option<syn::Ident>,
+    // Unit variant of the enum on the other side that a unit struct stands for
+    pub(crate) variant: Option<syn::Ident>,
+    // Function building the target from converted fields, instead of a struct literal
+    pub(crate) constructor: Option<Constructor>,
+    // Builder the target is built with, calling a setter per field
+    pub(crate) builder: Option<Builder>,
+    // Wether source fields are read through getter methods named after them
+    pub(crate) getters: bool,
+    // Wether the conversion is from a reference to the source, cloning its fields
+    pub(crate) by_ref: bool,
+    // How the other side's field and variant names derive from ours
+    pub(crate) naming: Naming,
+    // Renamed and skipped fields (or variants) listed on the conversion itself
+    pub(crate) mapping: FieldMapping,
+    // Wether every field of the source must be mapped, or listed in `ignore`
+    pub(crate) exhaustive: bool,
+    // Source fields left unmapped on purpose by an exhaustive conversion
+    pub(crate) ignore: Vec<syn::Ident>,
+    // Condition the generated impl is compiled under, from `cfg(feature = "x")`
+    pub(crate) cfg: Option<syn::Meta>,
+}
+
+/// Fields (or variants of an enum) renamed with `map(ours = "theirs")` and left out with
+/// `skip(ours)`, for types whose fields are awkward to annotate one by one
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FieldMapping {
+    map: FieldMap,
+    skip: PathList,
+}
+
+#[derive(Clone, Debug, Default)]
+struct FieldMap(Vec<(syn::Ident, syn::Ident)>);
+
+impl FromMeta for FieldMap {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                darling::ast::NestedMeta::Meta(meta @ syn::Meta::NameValue(name_value)) => Ok((
+                    name_value.path.require_ident()?.clone(),
+                    syn::Ident::from_meta(meta)?,
+                )),
+                _ => Err(darling::Error::unsupported_format("literal").with_span(item)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
+}
+
+impl FieldMapping {
+    /// The name `map` gives one of our fields or variants on the other side
+    pub(crate) fn renamed(&self, ident: &syn::Ident) -> Option<syn::Ident> {
+        self.map
+            .0
+            .iter()
+            .find(|(ours, _)| ours == ident)
+            .map(|(_, theirs)| theirs.clone())
+    }
+
+    pub(crate) fn skips(&self, ident: &syn::Ident) -> bool {
+        self.skip.iter().any(|path| path.is_ident(ident))
+    }
+
+    /// Fails on the first listed name that isn't one of `known`, the fields or variants
+    /// of the annotated type
+    pub(crate) fn check_names<'a>(
+        &self,
+        known: impl Iterator<Item = &'a syn::Ident> + Clone,
+        kind: &str,
+    ) -> syn::Result<()> {
+        let listed = self
+            .map
+            .0
+            .iter()
+            .map(|(ours, _)| (ours.to_string(), ours.span()))
+            .chain(self.skip.iter().map(|path| {
+                let name = path.get_ident().map(ToString::to_string);
+                (name.unwrap_or_default(), syn::spanned::Spanned::span(path))
+            }));
+        for (name, span) in listed {
+            if !known.clone().any(|ident| *ident == name) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("No {} named `{}` to map or skip", kind, name),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rules deriving the other side's field and variant names from ours, for the ones
+/// without a `rename`. Prefixes and suffixes are stripped from our name, which is then
+/// written in the case convention before new ones are added.
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct Naming {
+    // Case convention of the other side, from `rename_all = "camelCase"`
+    #[darling(default, with = identifier_rule)]
+    pub(crate) rename_all: Option<RenameRule>,
+    #[darling(default)]
+    pub(crate) strip_prefix: Option<String>,
+    #[darling(default)]
+    pub(crate) strip_suffix: Option<String>,
+    #[darling(default)]
+    pub(crate) add_prefix: Option<String>,
+    #[darling(default)]
+    pub(crate) add_suffix: Option<String>,
+    // Wether `#[serde(rename)]` and `#[serde(rename_all)]` name the other side
+    #[darling(default)]
+    pub(crate) use_serde_names: bool,
+    // The type's `#[serde(rename_all)]`, applied the way serde does unless `rename_all`
+    // is given
+    #[darling(skip)]
+    pub(crate) serde_rename_all: Option<RenameRule>,
+}
+
+impl Naming {
+    /// The name on the other side for one of our fields, from its serde name when
+    /// `use_serde_names` is given and it has one
+    pub(crate) fn field_name(
+        &self,
+        ident: &syn::Ident,
+        attrs: &[syn::Attribute],
+    ) -> syn::Result<syn::Ident> {
+        self.name_for(ident, attrs, apply_to_field)
+    }
+
+    /// The name on the other side for one of our variants, like `field_name`
+    pub(crate) fn variant_name(
+        &self,
+        ident: &syn::Ident,
+        attrs: &[syn::Attribute],
+    ) -> syn::Result<syn::Ident> {
+        self.name_for(ident, attrs, apply_to_variant)
+    }
+
+    fn name_for(
+        &self,
+        ident: &syn::Ident,
+        attrs: &[syn::Attribute],
+        serde_rule: fn(RenameRule, &str) -> String,
+    ) -> syn::Result<syn::Ident> {
+        if self.use_serde_names
+            && let Some(rename) = serde_rename(attrs)?
+        {
+            return rename.parse();
+        }
+        self.apply(ident, serde_rule)
+    }
+
+    fn apply(
+        &self,
+        ident: &syn::Ident,
+        serde_rule: fn(RenameRule, &str) -> String,
+    ) -> syn::Result<syn::Ident> {
+        if self.is_identity() {
+            return Ok(ident.clone());
+        }
+        let mut name = ident.unraw().to_string();
+        if let Some(stripped) = self
+            .strip_prefix
+            .as_ref()
+            .and_then(|prefix| name.strip_prefix(prefix.as_str()))
+        {
+            name = stripped.to_string();
+        }
+        if let Some(stripped) = self
+            .strip_suffix
+            .as_ref()
+            .and_then(|suffix| name.strip_suffix(suffix.as_str()))
+        {
+            name = stripped.to_string();
+        }
+        if let Some(rule) = self.rename_all {
+            name = rule.apply(&name);
+        } else if let Some(rule) = self.serde_rename_all {
+            name = serde_rule(rule, &name);
+        }
+        let prefix = self.add_prefix.as_deref().unwrap_or_default();
+        let suffix = self.add_suffix.as_deref().unwrap_or_default();
+        let name = format!("{}{}{}", prefix, name, suffix);
+        // Prefixes and suffixes may still leave a name that isn't an identifier
+        let mut renamed = syn::parse_str::<syn::Ident>(&name).map_err(|_| {
+            syn::Error::new(
+                ident.span(),
+                format!(
+                    "`{}` is renamed to `{}`, which is not an identifier",
+                    ident, name
+                ),
+            )
+        })?;
+        renamed.set_span(ident.span());
+        Ok(renamed)
+    }
+
+    fn is_identity(&self) -> bool {
+        self.rename_all.is_none()
+            && self.serde_rename_all.is_none()
+            && self.strip_prefix.is_none()
+            && self.strip_suffix.is_none()
+            && self.add_prefix.is_none()
+            && self.add_suffix.is_none()
+    }
+
+    // These rules, with the ones left out taken from `fallback`
+    fn or(self, fallback: &Naming) -> Naming {
+        Naming {
+            rename_all: self.rename_all.or(fallback.rename_all),
+            strip_prefix: self.strip_prefix.or(fallback.strip_prefix.clone()),
+            strip_suffix: self.strip_suffix.or(fallback.strip_suffix.clone()),
+            add_prefix: self.add_prefix.or(fallback.add_prefix.clone()),
+            add_suffix: self.add_suffix.or(fallback.add_suffix.clone()),
+            use_serde_names: self.use_serde_names || fallback.use_serde_names,
+            serde_rename_all: self.serde_rename_all.or(fallback.serde_rename_all),
+        }
+    }
+}
+
+// A `rename_all` for field and variant names, which rules out the kebab conventions
+fn identifier_rule(meta: &syn::Meta) -> darling::Result<Option<RenameRule>> {
+    let rule = RenameRule::from_meta(meta)?;
+    if matches!(rule, RenameRule::Kebab | RenameRule::ScreamingKebab) {
+        let error = darling::Error::custom("kebab-case names are not identifiers");
+        return Err(match meta {
+            syn::Meta::NameValue(name_value) => error.with_span(&name_value.value),
+            _ => error.with_span(meta),
+        });
+    }
+    Ok(Some(rule))
+}
+
+/// A builder the target is built with, from `builder = "T::builder"` and `build = "build"`
+#[derive(Clone, Debug)]
+pub(crate) struct Builder {
+    pub(crate) func: Path,
+    // Method finishing the builder, `build` unless given
+    pub(crate) build: syn::Ident,
+}
+
+/// A function the target is built with, from `constructor = "T::new"` or
+/// `try_constructor = "T::try_new"`, and the fields passed to it from `args(a, b)`
+#[derive(Clone, Debug)]
+pub(crate) struct Constructor {
+    pub(crate) func: Path,
+    // Wether `func` returns a `Result`
+    pub(crate) fallible: bool,
+    // Fields passed to `func` in order, or all fields when not given
+    pub(crate) args: Option<Vec<syn::Ident>>,
 }
 
 impl ConversionMeta {
-    pub(crate) fn other_type(&self) -> Path {
+    // The conversion one of the `into`, `from`, ... attributes asks for
+    fn from_attrs(
+        attr: ConvAttrs,
+        own_type: &Type,
+        method: ConversionMethod,
+        naming: &Naming,
+    ) -> darling::Result<Self> {
+        let (other_type, other_kind) = attr.other_type()?;
+        let constructor = attr.constructor()?;
+        let builder = attr.builder()?;
+        let ignore = attr.ignore()?;
+        let (source_name, target_name) = if method.is_from() {
+            (other_type, own_type.clone())
+        } else {
+            (own_type.clone(), other_type)
+        };
+        Ok(ConversionMeta {
+            source_name,
+            target_name,
+            method,
+            other_kind,
+            default_allowed: attr.default,
+            arity: attr.arity,
+            fill: attr.fill,
+            tag: attr.tag,
+            variant: attr.variant,
+            constructor,
+            builder,
+            getters: attr.getters,
+            by_ref: attr.by_ref,
+            naming: attr.naming.or(naming),
+            mapping: FieldMapping {
+                map: attr.map,
+                skip: attr.skip,
+            },
+            exhaustive: attr.exhaustive,
+            ignore,
+            cfg: attr.cfg,
+        })
+    }
+
+    // The type the generated impl converts from. A borrowed source lives as long as the
+    // first lifetime of a target view, as in `From<&'a User> for UserView<'a>`.
+    pub(crate) fn source_type(&self) -> Type {
+        let source_name = &self.source_name;
+        match lifetimes(&self.target_name).first() {
+            _ if !self.by_ref => source_name.clone(),
+            Some(lifetime) => syn::parse_quote!(&#lifetime #source_name),
+            None => syn::parse_quote!(&#source_name),
+        }
+    }
+
+    // Generic parameters of the generated impl, for the lifetimes of either side
+    pub(crate) fn impl_generics(&self) -> TokenStream2 {
+        let mut params = lifetimes(&self.source_name);
+        for lifetime in lifetimes(&self.target_name) {
+            if !params.contains(&lifetime) {
+                params.push(lifetime);
+            }
+        }
+        if params.is_empty() {
+            quote!()
+        } else {
+            quote!(<#(#params),*>)
+        }
+    }
+
+    // The generated impl, under the conversion's `cfg` if it has one
+    pub(crate) fn gated(&self, conversion_impl: TokenStream2) -> TokenStream2 {
+        match &self.cfg {
+            Some(cfg) => quote! { #[#cfg] #conversion_impl },
+            None => conversion_impl,
+        }
+    }
+
+    pub(crate) fn other_type(&self) -> Type {
         if self.method.is_from() {
             self.source_name.clone()
         } else {
@@ -20,6 +361,21 @@ impl ConversionMeta {
     }
 }
 
+/// A value for a position of an unnamed target that no field maps to, written as
+/// `fill(index = 3, value = "expr")`
+#[derive(FromMeta, Clone, Debug)]
+pub(crate) struct PositionFill {
+    pub(crate) index: usize,
+    pub(crate) value: syn::Expr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum OtherKind {
+    Path,
+    Tuple,
+    Array,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum ConversionMethod {
     Into,
@@ -49,11 +405,94 @@ fn ident_to_path(ident: &syn::Ident) -> syn::Path {
     }
 }
 
-#[derive(FromMeta, Debug)]
+pub(crate) fn path_to_type(path: Path) -> Type {
+    Type::Path(syn::TypePath { qself: None, path })
+}
+
+/// The type without generic arguments, to name it in expressions and patterns, where
+/// arguments like the lifetimes of `UserView<'a>` are inferred
+pub(crate) fn without_generics(ty: &Type) -> Type {
+    let mut ty = ty.clone();
+    if let Type::Path(type_path) = &mut ty {
+        for segment in type_path.path.segments.iter_mut() {
+            segment.arguments = syn::PathArguments::None;
+        }
+    }
+    ty
+}
+
+// The lifetimes given as generic arguments of a type, as in `UserView<'a>`
+fn lifetimes(ty: &Type) -> Vec<syn::Lifetime> {
+    let Type::Path(type_path) = ty else {
+        return Vec::new();
+    };
+    type_path
+        .path
+        .segments
+        .iter()
+        .flat_map(|segment| match &segment.arguments {
+            syn::PathArguments::AngleBracketed(arguments) => arguments.args.iter().collect(),
+            _ => Vec::new(),
+        })
+        .filter_map(|argument| match argument {
+            syn::GenericArgument::Lifetime(lifetime) => Some(lifetime.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(FromMeta, Clone, Debug)]
+#[darling(and_then = Self::validate)]
 struct ConvAttrs {
-    path: Path,
+    #[darling(default)]
+    path: Option<Path>,
+    // Several targets sharing the other options, each converted as if given by `path`
+    #[darling(default)]
+    paths: Vec<syn::LitStr>,
+    // Convert to or from a tuple of the fields, in field order
+    #[darling(default)]
+    tuple: bool,
+    // Convert to or from an array of the fields, which must all have the same type
+    #[darling(default)]
+    array: bool,
     #[darling(default)]
     default: bool,
+    // Number of positions of an unnamed target, for `default` to fill
+    #[darling(default)]
+    arity: Option<usize>,
+    #[darling(default, multiple)]
+    fill: Vec<PositionFill>,
+    #[darling(default)]
+    tag: Option<syn::Ident>,
+    #[darling(default)]
+    variant: Option<syn::Ident>,
+    #[darling(default)]
+    constructor: Option<Path>,
+    #[darling(default)]
+    try_constructor: Option<Path>,
+    #[darling(default)]
+    args: Option<PathList>,
+    #[darling(default)]
+    builder: Option<Path>,
+    #[darling(default)]
+    build: Option<syn::Ident>,
+    #[darling(default)]
+    getters: bool,
+    #[darling(default)]
+    by_ref: bool,
+    #[darling(flatten)]
+    naming: Naming,
+    #[darling(default)]
+    map: FieldMap,
+    #[darling(default)]
+    skip: PathList,
+    // Fail to compile when the source has fields that aren't mapped or ignored
+    #[darling(default)]
+    exhaustive: bool,
+    #[darling(default)]
+    ignore: PathList,
+    #[darling(default)]
+    cfg: Option<syn::Meta>,
 }
 
 #[derive(FromDeriveInput)]
@@ -71,58 +510,227 @@ struct Conversions {
 
     #[darling(default, multiple)]
     try_from: Vec<ConvAttrs>,
+
+    // Into and from conversions generated from a single mapping
+    #[darling(default, multiple)]
+    both: Vec<ConvAttrs>,
+
+    #[darling(default, multiple)]
+    try_both: Vec<ConvAttrs>,
+
+    // Integer types a fieldless enum converts to and from through its discriminants
+    #[darling(default)]
+    repr: PathList,
+
+    // Naming rules of the other side, for conversions without their own
+    #[darling(flatten)]
+    naming: Naming,
+
+    // Conversions between a fieldless enum and its variant names
+    #[darling(default)]
+    string: Option<Override<StringConversion>>,
+}
+
+/// Options of `#[convert(string)]`, written as `#[convert(string(case = "snake_case"))]`
+#[derive(FromMeta, Default, Clone, Debug)]
+pub(crate) struct StringConversion {
+    #[darling(default)]
+    pub(crate) case: Option<RenameRule>,
+}
+
+fn parse_conversions(ast: &DeriveInput) -> syn::Result<Conversions> {
+    Ok(Conversions::from_derive_input(ast)?)
+}
+
+/// The integer types given in `#[convert(repr(...))]`
+pub(crate) fn extract_reprs(ast: &DeriveInput) -> syn::Result<Vec<Path>> {
+    Ok(parse_conversions(ast)?.repr.to_vec())
+}
+
+/// The options of `#[convert(string)]`, if it was given
+pub(crate) fn extract_string_conversion(
+    ast: &DeriveInput,
+) -> syn::Result<Option<StringConversion>> {
+    Ok(parse_conversions(ast)?
+        .string
+        .map(Override::unwrap_or_default))
 }
 
-pub(crate) fn extract_conversions(ast: &DeriveInput) -> Vec<ConversionMeta> {
-    let conversions_data = match Conversions::from_derive_input(ast) {
-        Ok(v) => v,
-        Err(e) => {
-            // You'd typically emit this as a compile error
-            panic!("Error parsing conversion attributes: {}", e);
+impl ConvAttrs {
+    // Checks the combination of options when parsing, so that errors without a span of
+    // their own point at the attribute
+    fn validate(self) -> darling::Result<Self> {
+        for attr in self.clone().per_path()? {
+            attr.other_type()?;
+            attr.constructor()?;
+            attr.builder()?;
+            attr.ignore()?;
         }
-    };
+        Ok(self)
+    }
 
-    let mut result = Vec::new();
+    // The other side of the conversion. Tuples and arrays start out as `()`, until the
+    // struct conversion fills in the field types.
+    fn other_type(&self) -> darling::Result<(Type, OtherKind)> {
+        let kind = match (&self.path, self.tuple, self.array) {
+            (Some(_), false, false) => OtherKind::Path,
+            (None, true, false) => OtherKind::Tuple,
+            (None, false, true) => OtherKind::Array,
+            (path, _, _) => {
+                let error =
+                    darling::Error::custom("expected exactly one of `path`, `tuple` or `array`");
+                return Err(match path {
+                    Some(path) => error.with_span(path),
+                    None => error,
+                });
+            }
+        };
+        let other_type = match &self.path {
+            Some(path) => path_to_type(path.clone()),
+            None => syn::parse_quote!(()),
+        };
+        Ok((other_type, kind))
+    }
 
-    // Process "into" attribute
-    for attr in conversions_data.into {
-        result.push(ConversionMeta {
-            source_name: ident_to_path(&conversions_data.ident),
-            target_name: attr.path,
-            method: ConversionMethod::Into,
-            default_allowed: attr.default,
-        });
+    fn builder(&self) -> darling::Result<Option<Builder>> {
+        match (&self.builder, &self.build) {
+            (Some(func), _) if self.constructor.is_some() || self.try_constructor.is_some() => Err(
+                darling::Error::custom("expected only one of `builder` or a constructor")
+                    .with_span(func),
+            ),
+            (Some(func), build) => Ok(Some(Builder {
+                func: func.clone(),
+                build: build
+                    .clone()
+                    .unwrap_or_else(|| syn::Ident::new("build", proc_macro2::Span::call_site())),
+            })),
+            (None, Some(build)) => {
+                Err(darling::Error::custom("`build` requires `builder`").with_span(build))
+            }
+            (None, None) => Ok(None),
+        }
     }
 
-    // Process "try_into" attribute
-    for attr in conversions_data.try_into {
-        result.push(ConversionMeta {
-            source_name: ident_to_path(&conversions_data.ident),
-            target_name: attr.path,
-            method: ConversionMethod::TryInto,
-            default_allowed: attr.default,
-        });
+    // One set of attributes per target, with `paths` spread into a `path` each
+    fn per_path(self) -> darling::Result<Vec<ConvAttrs>> {
+        let Some(first) = self.paths.first() else {
+            return Ok(vec![self]);
+        };
+        if self.path.is_some() {
+            return Err(
+                darling::Error::custom("expected only one of `path` or `paths`").with_span(first),
+            );
+        }
+        self.paths
+            .iter()
+            .map(|path| {
+                Ok(ConvAttrs {
+                    path: Some(path.parse().map_err(|e| {
+                        darling::Error::custom(format!("invalid path in `paths`: {}", e))
+                            .with_span(path)
+                    })?),
+                    paths: Vec::new(),
+                    ..self.clone()
+                })
+            })
+            .collect()
     }
 
-    // Process "from" attribute
-    for attr in conversions_data.from {
-        result.push(ConversionMeta {
-            source_name: attr.path,
-            target_name: ident_to_path(&conversions_data.ident),
-            method: ConversionMethod::From,
-            default_allowed: attr.default,
-        });
+    fn ignore(&self) -> darling::Result<Vec<syn::Ident>> {
+        if let Some(first) = self.ignore.first()
+            && !self.exhaustive
+        {
+            return Err(darling::Error::custom("`ignore` requires `exhaustive`").with_span(first));
+        }
+        self.ignore
+            .iter()
+            .map(|path| match path.get_ident() {
+                Some(ident) => Ok(ident.clone()),
+                None => {
+                    Err(darling::Error::custom("`ignore` must list field names").with_span(path))
+                }
+            })
+            .collect()
     }
 
-    // Process "try_from" attribute
-    for attr in conversions_data.try_from {
-        result.push(ConversionMeta {
-            source_name: attr.path,
-            target_name: ident_to_path(&conversions_data.ident),
-            method: ConversionMethod::TryFrom,
-            default_allowed: attr.default,
-        });
+    fn constructor(&self) -> darling::Result<Option<Constructor>> {
+        let args =
+            match &self.args {
+                Some(args) => Some(
+                    args.iter()
+                        .map(|arg| match arg.get_ident() {
+                            Some(ident) => Ok(ident.clone()),
+                            None => Err(darling::Error::custom("`args` must list field names")
+                                .with_span(arg)),
+                        })
+                        .collect::<darling::Result<Vec<_>>>()?,
+                ),
+                None => None,
+            };
+        match (&self.constructor, &self.try_constructor) {
+            (Some(func), None) => Ok(Some(Constructor {
+                func: func.clone(),
+                fallible: false,
+                args,
+            })),
+            (None, Some(func)) => Ok(Some(Constructor {
+                func: func.clone(),
+                fallible: true,
+                args,
+            })),
+            (None, None) if args.is_some() => Err(darling::Error::custom(
+                "`args` requires `constructor` or `try_constructor`",
+            )),
+            (None, None) => Ok(None),
+            (Some(_), Some(func)) => Err(darling::Error::custom(
+                "expected only one of `constructor` or `try_constructor`",
+            )
+            .with_span(func)),
+        }
     }
+}
+
+pub(crate) fn extract_conversions(ast: &DeriveInput) -> syn::Result<Vec<ConversionMeta>> {
+    let conversions_data = parse_conversions(ast)?;
+    let own_type = path_to_type(ident_to_path(&conversions_data.ident));
+    let naming = conversions_data.naming;
 
-    result
+    let single = [
+        (conversions_data.into, ConversionMethod::Into),
+        (conversions_data.try_into, ConversionMethod::TryInto),
+        (conversions_data.from, ConversionMethod::From),
+        (conversions_data.try_from, ConversionMethod::TryFrom),
+    ]
+    .into_iter()
+    .flat_map(|(attrs, method)| attrs.into_iter().map(move |attr| (attr, vec![method])));
+
+    // `both` and `try_both` stand for a conversion in each direction
+    let paired = [
+        (
+            conversions_data.both,
+            [ConversionMethod::Into, ConversionMethod::From],
+        ),
+        (
+            conversions_data.try_both,
+            [ConversionMethod::TryInto, ConversionMethod::TryFrom],
+        ),
+    ]
+    .into_iter()
+    .flat_map(|(attrs, methods)| attrs.into_iter().map(move |attr| (attr, methods.to_vec())));
+
+    let mut conversions = Vec::new();
+    for (attr, methods) in single.chain(paired) {
+        for attr in attr.per_path()? {
+            for &method in &methods {
+                let mut conversion =
+                    ConversionMeta::from_attrs(attr.clone(), &own_type, method, &naming)?;
+                // Serde's case convention stands in for a `rename_all` of our own
+                if conversion.naming.use_serde_names && conversion.naming.rename_all.is_none() {
+                    conversion.naming.serde_rename_all = serde_rename_all(&ast.attrs)?;
+                }
+                conversions.push(conversion);
+            }
+        }
+    }
+    Ok(conversions)
 }
@@ -1,3 +1,5 @@
 pub(crate) mod conversion_enum;
 pub(crate) mod conversion_field;
 pub(crate) mod conversion_meta;
+pub(crate) mod rename_rule;
+pub(crate) mod serde_names;
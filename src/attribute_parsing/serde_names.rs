@@ -0,0 +1,111 @@
+use syn::{Attribute, LitStr, Token};
+
+use super::rename_rule::RenameRule;
+
+/// The name given by `#[serde(rename = "...")]` on a field or variant
+pub(crate) fn serde_rename(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    serde_value(attrs, "rename")
+}
+
+/// The case convention given by `#[serde(rename_all = "...")]` on a struct or enum
+pub(crate) fn serde_rename_all(attrs: &[Attribute]) -> syn::Result<Option<RenameRule>> {
+    serde_value(attrs, "rename_all")?
+        .map(|lit| {
+            RenameRule::from_name(&lit.value()).ok_or_else(|| {
+                syn::Error::new(
+                    lit.span(),
+                    format!("Unknown serde case convention `{}`", lit.value()),
+                )
+            })
+        })
+        .transpose()
+}
+
+// The string value of `key` in the `#[serde(...)]` attributes, skipping over the other
+// options serde takes
+fn serde_value(attrs: &[Attribute], key: &str) -> syn::Result<Option<LitStr>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                if !meta.input.peek(Token![=]) {
+                    return Err(meta.error(format!(
+                        "only `{} = \"...\"` is supported with use_serde_names",
+                        key
+                    )));
+                }
+                value = Some(meta.value()?.parse()?);
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(value)
+}
+
+/// A field name as serde's `rename_all` writes it. Serde expects fields in snake_case and,
+/// unlike our own rules, leaves their words alone.
+pub(crate) fn apply_to_field(rule: RenameRule, field: &str) -> String {
+    match rule {
+        RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+        RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+        RenameRule::Pascal => {
+            let mut pascal = String::new();
+            let mut capitalize = true;
+            for ch in field.chars() {
+                if ch == '_' {
+                    capitalize = true;
+                } else if capitalize {
+                    pascal.push(ch.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    pascal.push(ch);
+                }
+            }
+            pascal
+        }
+        RenameRule::Camel => lower_first(&apply_to_field(RenameRule::Pascal, field)),
+        RenameRule::Kebab => field.replace('_', "-"),
+        RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+    }
+}
+
+/// A variant name as serde's `rename_all` writes it. Serde expects variants in PascalCase
+/// and starts a new word at every uppercase letter, so `HTTPServer` becomes `h_t_t_p_server`.
+pub(crate) fn apply_to_variant(rule: RenameRule, variant: &str) -> String {
+    match rule {
+        RenameRule::Pascal => variant.to_owned(),
+        RenameRule::Lower => variant.to_ascii_lowercase(),
+        RenameRule::Upper => variant.to_ascii_uppercase(),
+        RenameRule::Camel => lower_first(variant),
+        RenameRule::Snake => {
+            let mut snake = String::new();
+            for (i, ch) in variant.char_indices() {
+                if i > 0 && ch.is_uppercase() {
+                    snake.push('_');
+                }
+                snake.push(ch.to_ascii_lowercase());
+            }
+            snake
+        }
+        RenameRule::ScreamingSnake => {
+            apply_to_variant(RenameRule::Snake, variant).to_ascii_uppercase()
+        }
+        RenameRule::Kebab => apply_to_variant(RenameRule::Snake, variant).replace('_', "-"),
+        RenameRule::ScreamingKebab => {
+            apply_to_variant(RenameRule::ScreamingSnake, variant).replace('_', "-")
+        }
+    }
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
@@ -1,29 +1,153 @@
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
-use syn::{DeriveInput, Path};
+use syn::{DeriveInput, Type};
 
 use crate::{
     attribute_parsing::{
-        conversion_field::{ConvertibleField, FieldConversionMethod},
-        conversion_meta::{ConversionMeta, extract_conversions},
+        conversion_field::{ConvertibleField, FieldConversionMethod, FieldIdentifier, Flatten},
+        conversion_meta::{
+            ConversionMeta, OtherKind, PositionFill, extract_conversions, extract_reprs,
+            extract_string_conversion,
+        },
+    },
+    enum_convert::{
+        implement_all_enum_conversions, implement_repr_conversions, implement_string_conversions,
     },
-    enum_convert::implement_all_enum_conversions,
     struct_convert::implement_all_struct_conversions,
 };
 
+/// Where the fields of the source value can be read from.
+#[derive(Clone, Copy)]
+pub(super) enum FieldSource<'a> {
+    /// Fields are accessed through `source.<field>`
+    Prefixed,
+    /// Fields are accessed through `source.<field>` of a borrowed source, and cloned
+    Borrowed,
+    /// Fields were bound by a match pattern, as in enum arms
+    Bound(&'a [ConvertibleField]),
+    /// Fields were bound by reference by matching a borrowed source, and are cloned
+    BoundRefs(&'a [ConvertibleField]),
+}
+
+impl<'a> FieldSource<'a> {
+    pub(super) fn prefixed(by_ref: bool) -> Self {
+        if by_ref {
+            FieldSource::Borrowed
+        } else {
+            FieldSource::Prefixed
+        }
+    }
+
+    pub(super) fn bound(fields: &'a [ConvertibleField], by_ref: bool) -> Self {
+        if by_ref {
+            FieldSource::BoundRefs(fields)
+        } else {
+            FieldSource::Bound(fields)
+        }
+    }
+
+    fn is_borrowed(&self) -> bool {
+        matches!(self, FieldSource::Borrowed | FieldSource::BoundRefs(_))
+    }
+
+    // The place `source_name` is read from, without cloning borrowed sources
+    fn place(&self, source_name: &FieldIdentifier) -> TokenStream2 {
+        match self {
+            FieldSource::Prefixed | FieldSource::Borrowed => quote!(source.#source_name),
+            FieldSource::Bound(_) | FieldSource::BoundRefs(_) => source_name.as_named(),
+        }
+    }
+
+    pub(super) fn access(&self, source_name: &FieldIdentifier) -> TokenStream2 {
+        let place = self.place(source_name);
+        if self.is_borrowed() {
+            quote!(#place.clone())
+        } else {
+            place
+        }
+    }
+
+    // Reads `source_name`, or calls its getter, followed by the nested members of
+    // `source_path`. Optional members are unwrapped by reference with `unwrap`, and values
+    // read by reference are cloned in the end, unless the field converts from a `borrow`.
+    fn access_path(
+        &self,
+        source_name: &FieldIdentifier,
+        source_path: &[(FieldIdentifier, bool)],
+        getter: Option<&syn::Ident>,
+        borrow: bool,
+        unwrap: impl Fn(TokenStream2) -> TokenStream2,
+    ) -> TokenStream2 {
+        let mut access = match getter {
+            Some(getter) => quote!(source.#getter()),
+            None => self.place(source_name),
+        };
+        for (i, (member, optional)) in source_path.iter().enumerate() {
+            if i > 0 {
+                access = quote!(#access.#member);
+            }
+            if *optional {
+                access = unwrap(access);
+            }
+        }
+
+        let read_by_ref = getter.is_some() || source_path.iter().any(|(_, optional)| *optional);
+        if borrow {
+            // Getters and bindings of a borrowed source are references already
+            match self {
+                _ if getter.is_some() => access,
+                FieldSource::BoundRefs(_) if source_path.len() <= 1 => access,
+                _ => quote!((&#access)),
+            }
+        } else if read_by_ref || self.is_borrowed() {
+            quote!(#access.clone())
+        } else {
+            access
+        }
+    }
+
+    // Arguments handed to field-level `with_func` conversion functions. In enum arms these
+    // are the bound fields, which leave out `default` ones since the source doesn't have them.
+    fn func_args(&self) -> TokenStream2 {
+        match self {
+            FieldSource::Prefixed => quote!(&source),
+            FieldSource::Borrowed => quote!(source),
+            FieldSource::Bound(fields) => {
+                let bindings = fields
+                    .iter()
+                    .filter(|f| !f.default)
+                    .map(|f| f.source_name.as_named());
+                quote!(#(&#bindings),*)
+            }
+            FieldSource::BoundRefs(fields) => {
+                let bindings = fields
+                    .iter()
+                    .filter(|f| !f.default)
+                    .map(|f| f.source_name.as_named());
+                quote!(#(#bindings),*)
+            }
+        }
+    }
+}
+
 pub(super) fn field_falliable_conversion(
     ConvertibleField {
         source_name,
+        ty: _,
         target_name,
         skip,
         method,
         span,
         default,
         conversion_func,
+        flatten,
+        source_path,
+        getter,
+        borrow,
     }: ConvertibleField,
-    target_type: &Path,
+    target_type: &Type,
     named: bool,
-    source_prefix: bool,
+    field_source: FieldSource,
 ) -> TokenStream2 {
     if skip {
         return quote! {};
@@ -35,28 +159,53 @@ pub(super) fn field_falliable_conversion(
         quote! {}
     };
 
-    let source_name = if source_prefix {
-        quote!(source.#source_name)
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
     } else {
-        let source_name = source_name.as_named();
-        quote!(#source_name)
+        quote!(format!)
     };
 
+    let func_args = field_source.func_args();
+    let source_name = field_source.access_path(
+        &source_name,
+        &source_path,
+        getter.as_ref(),
+        borrow,
+        |member| {
+            quote! {
+                #member.as_ref().ok_or_else(||
+                    #error_creator("Failed trying to convert {} to {}: None value",
+                        stringify!(#member),
+                        stringify!(#target_type),
+                    )
+                )?
+            }
+        },
+    );
+
     if default {
         return quote_spanned! { span =>
             #named_start Default::default(),
         };
     }
 
-    let error_creator = if cfg!(feature = "anyhow") {
-        quote!(anyhow::anyhow!)
-    } else {
-        quote!(format!)
-    };
+    if let Some(flatten) = flatten {
+        return flattened_conversion(flatten, named_start, &source_name, field_source, |expr| {
+            quote_spanned! { span =>
+                #expr.try_into().map_err(|e|
+                    #error_creator("Failed trying to convert {} to {}: {:?}",
+                        stringify!(#expr),
+                        stringify!(#target_type),
+                        e,
+                    )
+                )?
+            }
+        });
+    }
 
     if let Some(func) = conversion_func {
         return quote_spanned! { span =>
-            #named_start #func(&source).map_err(|e|
+            #named_start #func(#func_args).map_err(|e|
                     #error_creator("Failed trying to convert {} to {}: {:?}",
                         stringify!(#source_name),
                         stringify!(#target_type),
@@ -78,9 +227,15 @@ pub(super) fn field_falliable_conversion(
 
     // Then use it in each match arm
     match method {
-        FieldConversionMethod::Plain => quote_spanned! { span =>
+        FieldConversionMethod::Plain | FieldConversionMethod::IntToEnum => quote_spanned! { span =>
             #named_start #source_name.try_into().#map_err?,
         },
+        FieldConversionMethod::AsRef => quote_spanned! { span =>
+            #named_start #source_name.as_ref(),
+        },
+        FieldConversionMethod::AsDeref => quote_spanned! { span =>
+            #named_start #source_name.as_deref(),
+        },
         FieldConversionMethod::UnwrapOption => {
             quote_spanned! { span =>
                 #named_start #source_name.ok_or_else(||
@@ -137,16 +292,21 @@ pub(super) fn field_falliable_conversion(
 pub(super) fn field_infalliable_conversion(
     ConvertibleField {
         source_name,
+        ty: _,
         target_name,
         skip,
         method,
         span,
         default,
         conversion_func,
+        flatten,
+        source_path,
+        getter,
+        borrow,
     }: ConvertibleField,
-    target_type: &Path,
+    target_type: &Type,
     named: bool,
-    source_prefix: bool,
+    field_source: FieldSource,
 ) -> TokenStream2 {
     if skip {
         return quote! {};
@@ -157,12 +317,23 @@ pub(super) fn field_infalliable_conversion(
         quote! {}
     };
 
-    let source_name = if source_prefix {
-        quote!(source.#source_name)
-    } else {
-        let source_name = source_name.as_named();
-        quote!(#source_name)
-    };
+    let func_args = field_source.func_args();
+    let source_name = field_source.access_path(
+        &source_name,
+        &source_path,
+        getter.as_ref(),
+        borrow,
+        |member| {
+            quote! {
+                #member.as_ref().expect(
+                    format!("Expected {} to exist when converting to {}",
+                        stringify!(#member),
+                        stringify!(#target_type),
+                    ).as_str()
+                )
+            }
+        },
+    );
 
     if default {
         return quote_spanned! { span =>
@@ -170,9 +341,15 @@ pub(super) fn field_infalliable_conversion(
         };
     }
 
+    if let Some(flatten) = flatten {
+        return flattened_conversion(flatten, named_start, &source_name, field_source, |expr| {
+            quote_spanned! { span => #expr.into() }
+        });
+    }
+
     if let Some(func) = conversion_func {
         return quote_spanned! { span =>
-            #named_start #func(&source),
+            #named_start #func(#func_args),
         };
     }
 
@@ -180,6 +357,12 @@ pub(super) fn field_infalliable_conversion(
         FieldConversionMethod::Plain => quote_spanned! { span =>
             #named_start #source_name.into(),
         },
+        FieldConversionMethod::AsRef => quote_spanned! { span =>
+            #named_start #source_name.as_ref(),
+        },
+        FieldConversionMethod::AsDeref => quote_spanned! { span =>
+            #named_start #source_name.as_deref(),
+        },
         FieldConversionMethod::UnwrapOption => {
             quote_spanned! { span =>
                 #named_start #source_name.expect(
@@ -215,35 +398,166 @@ pub(super) fn field_infalliable_conversion(
                 #named_start #source_name.into_iter().map(|(a, b)| (a.into(), b.into())).collect(),
             }
         }
+        FieldConversionMethod::IntToEnum => {
+            unreachable!("i32 to enum conversions are rejected unless they can fail")
+        }
+    }
+}
+
+fn flattened_conversion(
+    flatten: Flatten,
+    named_start: TokenStream2,
+    source_name: &TokenStream2,
+    field_source: FieldSource,
+    convert: impl Fn(TokenStream2) -> TokenStream2,
+) -> TokenStream2 {
+    match flatten {
+        Flatten::Spread(fields) => {
+            let conversions = fields.iter().map(|(nested, flat)| {
+                let conversion = convert(quote!(#source_name.#nested));
+                quote! { #flat: #conversion, }
+            });
+            quote! { #(#conversions)* }
+        }
+        Flatten::Regroup(nested_type, fields) => {
+            let conversions = fields.iter().map(|(nested, flat)| {
+                let conversion =
+                    convert(field_source.access(&FieldIdentifier::Named(flat.clone())));
+                quote! { #nested: #conversion, }
+            });
+            quote! { #named_start #nested_type { #(#conversions)* }, }
+        }
     }
 }
 
 pub(super) fn build_field_conversions(
     meta: &ConversionMeta,
     named: bool,
-    source_prefix: bool,
+    field_source: FieldSource,
     fields: &[ConvertibleField],
 ) -> syn::Result<Vec<TokenStream2>> {
     Ok(fields
         .iter()
         .map(|field| {
             if meta.method.is_falliable() {
-                field_falliable_conversion(field.clone(), &meta.target_name, named, source_prefix)
+                field_falliable_conversion(field.clone(), &meta.target_name, named, field_source)
             } else {
-                field_infalliable_conversion(field.clone(), &meta.target_name, named, source_prefix)
+                field_infalliable_conversion(field.clone(), &meta.target_name, named, field_source)
             }
         })
         .collect())
 }
 
+/// Places the conversions of positional fields at their target index, and fills every other
+/// position below `arity` with its `fill` value or `Default::default()`.
+pub(super) fn fill_positions(
+    fields: &[ConvertibleField],
+    conversions: Vec<TokenStream2>,
+    arity: usize,
+    fill: &[PositionFill],
+    span: Span,
+) -> syn::Result<Vec<TokenStream2>> {
+    let mut positions: Vec<Option<TokenStream2>> = vec![None; arity];
+    for (field, conversion) in fields.iter().zip(conversions) {
+        match field.target_name.index() {
+            Some(index) if index < arity => positions[index] = Some(conversion),
+            _ => {
+                return Err(syn::Error::new(
+                    field.span,
+                    format!("Field position is out of range for `arity = {}`", arity),
+                ));
+            }
+        }
+    }
+    for PositionFill { index, value } in fill {
+        match positions.get_mut(*index) {
+            Some(position @ None) => *position = Some(quote! { #value, }),
+            Some(Some(_)) => {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    format!("Position {} is already set by a field", index),
+                ));
+            }
+            None => {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    format!("Position {} is out of range for `arity = {}`", index, arity),
+                ));
+            }
+        }
+    }
+    Ok(positions
+        .into_iter()
+        .map(|position| position.unwrap_or_else(|| quote_spanned! { span => Default::default(), }))
+        .collect())
+}
+
 pub(super) fn try_convert_derive(ast: &DeriveInput) -> syn::Result<TokenStream2> {
-    let conversions = extract_conversions(ast);
+    let conversions = extract_conversions(ast)?;
+    let reprs = extract_reprs(ast)?;
+    let string_conversion = extract_string_conversion(ast)?;
 
     match &ast.data {
+        syn::Data::Struct(_) if !reprs.is_empty() => Err(syn::Error::new_spanned(
+            ast.ident.clone(),
+            "`repr` conversions are only supported for enums".to_string(),
+        )),
+        syn::Data::Struct(_) if string_conversion.is_some() => Err(syn::Error::new_spanned(
+            ast.ident.clone(),
+            "`string` conversions are only supported for enums".to_string(),
+        )),
         syn::Data::Struct(data_struct) => {
             implement_all_struct_conversions(data_struct, conversions)
         }
-        syn::Data::Enum(data_enum) => implement_all_enum_conversions(data_enum, conversions),
+        syn::Data::Enum(_)
+            if conversions
+                .iter()
+                .any(|conversion| conversion.other_kind != OtherKind::Path) =>
+        {
+            Err(syn::Error::new_spanned(
+                ast.ident.clone(),
+                "`tuple` and `array` conversions are only supported for structs".to_string(),
+            ))
+        }
+        syn::Data::Enum(_)
+            if conversions
+                .iter()
+                .any(|conversion| conversion.variant.is_some()) =>
+        {
+            Err(syn::Error::new_spanned(
+                ast.ident.clone(),
+                "`variant` conversions are only supported for unit structs".to_string(),
+            ))
+        }
+        syn::Data::Enum(_)
+            if conversions.iter().any(|conversion| {
+                conversion.constructor.is_some()
+                    || conversion.builder.is_some()
+                    || conversion.getters
+            }) =>
+        {
+            Err(syn::Error::new_spanned(
+                ast.ident.clone(),
+                "Constructors, builders and getters are only supported for structs".to_string(),
+            ))
+        }
+        syn::Data::Enum(_) if conversions.iter().any(|conversion| conversion.exhaustive) => {
+            Err(syn::Error::new_spanned(
+                ast.ident.clone(),
+                "`exhaustive` conversions are only supported for structs".to_string(),
+            ))
+        }
+        syn::Data::Enum(data_enum) => {
+            let conversions = implement_all_enum_conversions(data_enum, conversions)?;
+            let repr_conversions = implement_repr_conversions(&ast.ident, data_enum, &reprs)?;
+            let string_conversions =
+                implement_string_conversions(&ast.ident, data_enum, string_conversion)?;
+            Ok(quote! {
+                #conversions
+                #repr_conversions
+                #string_conversions
+            })
+        }
         syn::Data::Union(_) => Err(syn::Error::new_spanned(
             ast.ident.clone(),
             "Unions are not supported".to_string(),
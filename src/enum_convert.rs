@@ -1,13 +1,18 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::DataEnum;
+use syn::{DataEnum, Ident, Path};
 
 use crate::{
     attribute_parsing::{
-        conversion_enum::{ConversionVariant, extract_enum_variants},
-        conversion_meta::ConversionMeta,
+        conversion_enum::{
+            ConversionVariant, extract_enum_variants, extract_variant_spellings,
+            extract_variant_values,
+        },
+        conversion_field::ConvertibleField,
+        conversion_meta::{ConversionMeta, StringConversion, without_generics},
     },
-    derive_into::build_field_conversions,
+    derive_into::{FieldSource, build_field_conversions, fill_positions},
+    struct_convert::sorted_for_struct,
 };
 
 pub(super) fn implement_all_enum_conversions(
@@ -17,9 +22,19 @@ pub(super) fn implement_all_enum_conversions(
     let conversion_impls: Vec<_> = conversions
         .into_iter()
         .map(|conversion| {
-            let variants =
-                extract_enum_variants(data_enum, conversion.method, &conversion.other_type())?;
-            implement_enum_conversion(conversion.clone(), &variants)
+            let variants = extract_enum_variants(
+                data_enum,
+                conversion.method,
+                &conversion.other_type(),
+                &conversion.naming,
+                &conversion.mapping,
+            )?;
+            let conversion_impl = if conversion.tag.is_some() {
+                implement_tagged_conversion(conversion.clone(), &variants)?
+            } else {
+                implement_enum_conversion(conversion.clone(), &variants)?
+            };
+            Ok::<_, syn::Error>(conversion.gated(conversion_impl))
         })
         .collect::<Result<_, _>>()?;
 
@@ -37,7 +52,13 @@ fn implement_enum_conversion(
         target_name,
         method,
         default_allowed,
+        by_ref,
+        ..
     } = meta.clone();
+    let source_type = meta.source_type();
+    let impl_generics = meta.impl_generics();
+    let source_path = without_generics(&meta.source_name);
+    let target_path = without_generics(&meta.target_name);
 
     let default_fields = if default_allowed {
         quote! { ..Default::default() }
@@ -45,46 +66,140 @@ fn implement_enum_conversion(
         quote! {}
     };
 
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
     let variant_conversions = variants.iter().map(|variant| {
         let ConversionVariant {
             source_name: source_variant_name,
             target_name: target_variant_name,
-            named_variant,
+            source_named,
+            target_named,
             fields,
+            conversion_func,
+            func_by_ref,
+            discard_fields,
+            filled_fields,
+            arity,
+            fill,
         } = variant;
 
-        let source_fields = fields.iter().map(|f| f.source_name.as_named());
+        if ((arity.is_some() && !method.is_from()) || !fill.is_empty())
+            && (*target_named || !default_allowed)
+        {
+            return Err(syn::Error::new(
+                source_variant_name.span(),
+                "`arity` and `fill` require `default` and an unnamed target variant",
+            ));
+        }
 
-        let field_conversions =
-            build_field_conversions(&meta, *named_variant, false, fields).unwrap();
+        if *discard_fields {
+            return Ok(quote! {
+                #source_path::#source_variant_name { .. } => #target_path::#target_variant_name,
+            });
+        }
 
-        if variant.fields.is_empty() {
-            return quote! {
-                #source_name::#source_variant_name => #target_name::#target_variant_name,
+        if let Some(filled_fields) = filled_fields {
+            let values = filled_fields.iter().map(|(name, value)| {
+                if *target_named {
+                    quote! { #name: #value }
+                } else {
+                    quote! { #value }
+                }
+            });
+            let values = if *target_named {
+                quote! { { #(#values),* } }
+            } else {
+                quote! { (#(#values),*) }
             };
+            return Ok(quote! {
+                #source_path::#source_variant_name => #target_path::#target_variant_name #values,
+            });
         }
 
-        if variant.named_variant {
-            quote! {
-                #source_name::#source_variant_name{ #(#source_fields),* } => #target_name::#target_variant_name {
+        let pattern = if fields.is_empty() {
+            quote! { #source_path::#source_variant_name { .. } }
+        } else {
+            let bindings = source_pattern(fields, *source_named);
+            quote! { #source_path::#source_variant_name #bindings }
+        };
+
+        if let Some(func) = conversion_func {
+            let args = variant_func_args(fields, *func_by_ref, by_ref);
+
+            return Ok(if method.is_falliable() {
+                quote! {
+                    #pattern => #func(#(#args),*).map_err(|e|
+                        #error_creator("Failed trying to convert {} to {}: {:?}",
+                            stringify!(#source_name::#source_variant_name),
+                            stringify!(#target_name),
+                            e,
+                        )
+                    )?,
+                }
+            } else {
+                quote! {
+                    #pattern => #func(#(#args),*),
+                }
+            });
+        }
+
+        if fields.is_empty() {
+            return Ok(quote! {
+                #pattern => #target_path::#target_variant_name,
+            });
+        }
+
+        if *target_named {
+            let field_conversions = build_field_conversions(
+                &meta,
+                true,
+                FieldSource::bound(fields, by_ref),
+                &sorted_for_struct(true, fields.clone()),
+            )?;
+            Ok(quote! {
+                #pattern => #target_path::#target_variant_name {
                     #(#field_conversions)*
                     #default_fields
                 },
-            }
+            })
         } else {
-            quote! {
-                #source_name::#source_variant_name(#(#source_fields),*) => {
-                    #target_name::#target_variant_name(#(#field_conversions)*)
-                },
+            let mut positional = fields.clone();
+            positional.sort_by_key(|f| f.target_name.index());
+            let mut field_conversions =
+                build_field_conversions(&meta, false, FieldSource::bound(fields, by_ref), &positional)?;
+            if default_allowed && let Some(arity) = arity {
+                field_conversions = fill_positions(
+                    &positional,
+                    field_conversions,
+                    *arity,
+                    fill,
+                    source_variant_name.span(),
+                )?;
             }
+            Ok(quote! {
+                #pattern => {
+                    #target_path::#target_variant_name(#(#field_conversions)*)
+                },
+            })
         }
-    });
+    })
+    .collect::<syn::Result<Vec<_>>>()?;
+
+    let error_type = if cfg!(feature = "anyhow") {
+        quote! { anyhow::Error }
+    } else {
+        quote! { String }
+    };
 
     Ok(if method.is_falliable() {
         quote! {
-            impl TryFrom<#source_name> for #target_name {
-                type Error = String;
-                fn try_from(source: #source_name) -> Result<#target_name, Self::Error> {
+            impl #impl_generics TryFrom<#source_type> for #target_name {
+                type Error = #error_type;
+                fn try_from(source: #source_type) -> Result<#target_name, Self::Error> {
                     Ok(
                         match source {
                             #(#variant_conversions)*
@@ -95,8 +210,8 @@ fn implement_enum_conversion(
         }
     } else {
         quote! {
-            impl From<#source_name> for #target_name {
-                fn from(source: #source_name) -> #target_name {
+            impl #impl_generics From<#source_type> for #target_name {
+                fn from(source: #source_type) -> #target_name {
                     match source {
                         #(#variant_conversions)*
                     }
@@ -105,3 +220,374 @@ fn implement_enum_conversion(
         }
     })
 }
+
+// Arguments handed to variant-level `with_func` functions, the fields bound by
+// `source_pattern`. Matching a borrowed enum binds references, which are cloned unless the
+// function takes them by reference.
+fn variant_func_args(
+    fields: &[ConvertibleField],
+    func_by_ref: bool,
+    by_ref: bool,
+) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .filter(|f| !f.default)
+        .map(|f| {
+            let binding = f.source_name.as_named();
+            match (func_by_ref, by_ref) {
+                (true, false) => quote! { &#binding },
+                (false, true) => quote! { #binding.clone() },
+                _ => binding,
+            }
+        })
+        .collect()
+}
+
+/// Converts between an enum and a flat struct whose `tag` field names the variant. Each
+/// variant fills the struct fields it maps to and leaves the rest to `Default`, and the
+/// reverse conversion dispatches on the tag.
+fn implement_tagged_conversion(
+    meta: ConversionMeta,
+    variants: &[ConversionVariant],
+) -> syn::Result<TokenStream2> {
+    let ConversionMeta {
+        source_name,
+        target_name,
+        method,
+        tag,
+        by_ref,
+        ..
+    } = meta.clone();
+    let source_type = meta.source_type();
+    let impl_generics = meta.impl_generics();
+    let source_path = without_generics(&meta.source_name);
+    let target_path = without_generics(&meta.target_name);
+    let tag = tag.expect("tagged conversions have a tag");
+    let is_from = method.is_from();
+    if is_from && !method.is_falliable() {
+        return Err(syn::Error::new(
+            tag.span(),
+            "Converting from a tag can fail, use `try_from`",
+        ));
+    }
+
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    let error_type = if cfg!(feature = "anyhow") {
+        quote! { anyhow::Error }
+    } else {
+        quote! { String }
+    };
+
+    let variant_conversions = variants
+        .iter()
+        .map(|variant| {
+            let ConversionVariant {
+                source_name: source_variant_name,
+                target_name: target_variant_name,
+                source_named,
+                target_named,
+                fields,
+                conversion_func,
+                func_by_ref,
+                discard_fields,
+                ..
+            } = variant;
+
+            let struct_named = if is_from { source_named } else { target_named };
+            if !fields.is_empty() && !struct_named {
+                return Err(syn::Error::new(
+                    tag.span(),
+                    format!(
+                        "Variant {} needs field names to convert to a struct, use `fields = [...]`",
+                        if is_from {
+                            target_variant_name
+                        } else {
+                            source_variant_name
+                        }
+                    ),
+                ));
+            }
+
+            let func_call = |args: TokenStream2| {
+                let func = conversion_func.as_ref()?;
+                Some(if method.is_falliable() {
+                    quote! {
+                        #func(#args).map_err(|e|
+                            #error_creator("Failed trying to convert {} to {}: {:?}",
+                                stringify!(#source_name),
+                                stringify!(#target_name),
+                                e,
+                            )
+                        )?
+                    }
+                } else {
+                    quote! { #func(#args) }
+                })
+            };
+
+            if is_from {
+                // The struct is the source, dispatch on its tag
+                let tag_value = source_variant_name.to_string();
+                let args = match (*func_by_ref, by_ref) {
+                    (true, false) => quote! { &source },
+                    (false, true) => quote! { source.clone() },
+                    _ => quote! { source },
+                };
+                if let Some(call) = func_call(args) {
+                    return Ok(quote! { #tag_value => #call, });
+                }
+
+                let construction = if fields.is_empty() {
+                    quote! { #target_path::#target_variant_name }
+                } else if *target_named {
+                    let field_conversions = build_field_conversions(
+                        &meta,
+                        true,
+                        FieldSource::prefixed(by_ref),
+                        &sorted_for_struct(true, fields.clone()),
+                    )?;
+                    quote! { #target_path::#target_variant_name { #(#field_conversions)* } }
+                } else {
+                    let mut positional = fields.clone();
+                    positional.sort_by_key(|field| field.target_name.index());
+                    let field_conversions = build_field_conversions(
+                        &meta,
+                        false,
+                        FieldSource::prefixed(by_ref),
+                        &positional,
+                    )?;
+                    quote! { #target_path::#target_variant_name(#(#field_conversions)*) }
+                };
+                return Ok(quote! { #tag_value => #construction, });
+            }
+
+            // The enum is the source, fill the struct for each variant
+            let tag_value = target_variant_name.to_string();
+            let pattern = if fields.is_empty() || *discard_fields {
+                quote! { #source_path::#source_variant_name { .. } }
+            } else {
+                let bindings = source_pattern(fields, *source_named);
+                quote! { #source_path::#source_variant_name #bindings }
+            };
+
+            let args = variant_func_args(fields, *func_by_ref, by_ref);
+            if let Some(call) = func_call(quote! { #(#args),* }) {
+                return Ok(quote! { #pattern => #call, });
+            }
+
+            let field_conversions = if *discard_fields {
+                Vec::new()
+            } else {
+                build_field_conversions(&meta, true, FieldSource::bound(fields, by_ref), fields)?
+            };
+            Ok(quote! {
+                #pattern => #target_path {
+                    #tag: #tag_value.into(),
+                    #(#field_conversions)*
+                    ..Default::default()
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let body = if is_from {
+        quote! {
+            match AsRef::<str>::as_ref(&source.#tag) {
+                #(#variant_conversions)*
+                other => return Err(#error_creator("Failed trying to convert {} to {}: unknown {} {:?}",
+                    stringify!(#source_name),
+                    stringify!(#target_name),
+                    stringify!(#tag),
+                    other,
+                )),
+            }
+        }
+    } else {
+        quote! {
+            match source {
+                #(#variant_conversions)*
+            }
+        }
+    };
+
+    Ok(if method.is_falliable() {
+        quote! {
+            impl #impl_generics TryFrom<#source_type> for #target_name {
+                type Error = #error_type;
+                fn try_from(source: #source_type) -> Result<#target_name, Self::Error> {
+                    Ok(#body)
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics From<#source_type> for #target_name {
+                fn from(source: #source_type) -> #target_name {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+// Binds the fields read from the source variant. Fields that don't read the source (like
+// `default` ones) are left out, and the rest of the variant is matched with `..`.
+fn source_pattern(fields: &[ConvertibleField], source_named: bool) -> TokenStream2 {
+    let bound: Vec<_> = fields.iter().filter(|f| !f.default).collect();
+
+    if source_named {
+        let bindings = bound.iter().map(|f| f.source_name.as_named());
+        return quote! { { #(#bindings,)* .. } };
+    }
+
+    let len = bound
+        .iter()
+        .filter_map(|f| f.source_name.index())
+        .max()
+        .map_or(0, |max| max + 1);
+    let bindings = (0..len).map(|i| {
+        bound
+            .iter()
+            .find(|f| f.source_name.index() == Some(i))
+            .map_or_else(|| quote! { _ }, |f| f.source_name.as_named())
+    });
+    quote! { (#(#bindings,)* ..) }
+}
+
+/// Implements `From<Enum> for Int` and `TryFrom<Int> for Enum` for every integer type listed
+/// in `#[convert(repr(...))]`.
+pub(super) fn implement_repr_conversions(
+    enum_name: &Ident,
+    data_enum: &DataEnum,
+    reprs: &[Path],
+) -> syn::Result<TokenStream2> {
+    if reprs.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let values = extract_variant_values(data_enum)?;
+    let variant_names: Vec<_> = values.iter().map(|(name, _)| name).collect();
+    let variant_values: Vec<_> = values.iter().map(|(_, value)| value).collect();
+
+    let error_type = if cfg!(feature = "anyhow") {
+        quote! { anyhow::Error }
+    } else {
+        quote! { String }
+    };
+
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    let impls = reprs.iter().map(|repr| {
+        quote! {
+            impl From<#enum_name> for #repr {
+                fn from(source: #enum_name) -> #repr {
+                    match source {
+                        #(#enum_name::#variant_names => #variant_values,)*
+                    }
+                }
+            }
+
+            impl TryFrom<#repr> for #enum_name {
+                type Error = #error_type;
+                fn try_from(source: #repr) -> Result<#enum_name, Self::Error> {
+                    match source {
+                        #(value if value == #variant_values => Ok(#enum_name::#variant_names),)*
+                        value => Err(#error_creator("Failed trying to convert {} to {}: unknown value",
+                            value,
+                            stringify!(#enum_name),
+                        )),
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #(#impls)*
+    })
+}
+
+/// Implements conversions between a fieldless enum and its variant names: `From<Enum>` for
+/// `&'static str` and `String`, `TryFrom<&str>`, `FromStr` and `Display`.
+pub(super) fn implement_string_conversions(
+    enum_name: &Ident,
+    data_enum: &DataEnum,
+    string_conversion: Option<StringConversion>,
+) -> syn::Result<TokenStream2> {
+    let Some(StringConversion { case }) = string_conversion else {
+        return Ok(quote! {});
+    };
+
+    let spellings = extract_variant_spellings(data_enum, case)?;
+    let variant_names: Vec<_> = spellings.iter().map(|spelling| &spelling.ident).collect();
+    let names: Vec<_> = spellings.iter().map(|spelling| &spelling.name).collect();
+    let parsed_names = spellings.iter().map(|spelling| {
+        let names = std::iter::once(&spelling.name).chain(&spelling.aliases);
+        quote! { #(#names)|* }
+    });
+
+    let error_type = if cfg!(feature = "anyhow") {
+        quote! { anyhow::Error }
+    } else {
+        quote! { String }
+    };
+
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    Ok(quote! {
+        impl From<#enum_name> for &'static str {
+            fn from(source: #enum_name) -> &'static str {
+                match source {
+                    #(#enum_name::#variant_names => #names,)*
+                }
+            }
+        }
+
+        impl From<#enum_name> for String {
+            fn from(source: #enum_name) -> String {
+                <&'static str>::from(source).to_string()
+            }
+        }
+
+        impl TryFrom<&str> for #enum_name {
+            type Error = #error_type;
+            fn try_from(source: &str) -> Result<#enum_name, Self::Error> {
+                match source {
+                    #(#parsed_names => Ok(#enum_name::#variant_names),)*
+                    value => Err(#error_creator("Failed trying to convert {:?} to {}: unknown value",
+                        value,
+                        stringify!(#enum_name),
+                    )),
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for #enum_name {
+            type Err = #error_type;
+            fn from_str(source: &str) -> Result<#enum_name, Self::Err> {
+                #enum_name::try_from(source)
+            }
+        }
+
+        impl ::std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    #(#enum_name::#variant_names => #names,)*
+                })
+            }
+        }
+    })
+}
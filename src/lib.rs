@@ -47,6 +47,23 @@ mod util;
  | `#[convert(from(path = "Type"))]` | Implements `From<Type> for Self` |
  | `#[convert(try_into(path = "Type"))]` | Implements `TryFrom<Self> for Type` |
  | `#[convert(try_from(path = "Type"))]` | Implements `TryFrom<Type> for Self` |
+ | `#[convert(both(path = "Type"))]` | Implements `From` in both directions from one mapping (`try_both` for `TryFrom`) |
+ | `#[convert(into(paths = ["A", "B"]))]` | Implements the same conversion for each listed type, as if each were given by `path` |
+ | `#[convert(string)]` | Implements conversions to and from the variant names of fieldless enums, `FromStr` and `Display` |
+ | `#[convert(into(path = "Type", rename_all = "camelCase"))]` | Names the other side's fields or variants after ours in a case convention (`#[convert(rename_all = "...")]` for every conversion) |
+ | `#[convert(into(path = "Type", strip_prefix = "x_", add_prefix = "y_"))]` | Rewrites the prefixes of the other side's field or variant names (`strip_suffix` and `add_suffix` likewise) |
+ | `#[convert(into(path = "Type", use_serde_names))]` | Names the other side's fields and variants after their `#[serde(rename)]` and the type's `#[serde(rename_all)]` |
+ | `#[convert(into(path = "Type", map(ours = "theirs"), skip(a)))]` | Renames and skips fields or variants from the conversion itself |
+ | `#[convert(from(path = "Type", exhaustive, ignore(a)))]` | Fails to compile when the source has fields that are neither mapped nor ignored |
+ | `#[convert(into(path = "Type", cfg(feature = "x")))]` | Generates the conversion only when the `cfg` condition holds |
+ | `#[convert(into(tuple))]` | Implements `From<Self> for (A, B, ...)` from the struct's field types |
+ | `#[convert(into(array))]` | Implements `From<Self> for [T; N]` for structs whose fields all have type `T` |
+ | `#[convert(into(path = "Type", constructor = "Type::new", args(a, b)))]` | Builds the target by passing the converted fields to a constructor (`try_constructor` for one returning `Result`) |
+ | `#[convert(into(path = "Type", builder = "Type::builder"))]` | Builds the target with a setter call per field, then `build` (renamed with `build = "..."`) |
+ | `#[convert(into(path = "Type", by_ref))]` | Implements `From<&Self> for Type`, cloning fields before converting them |
+ | `#[convert(into(path = "Enum", variant = "Idle"))]` | Converts a unit struct into the unit variant `Enum::Idle` (`try_from` converts back) |
+ | `#[convert(into(path = "Type", tag = "kind"))]` | Converts an enum to a struct whose `kind` field names the variant (`try_from` dispatches on it) |
+ | `#[convert(repr(i32))]` | Implements `From<Self> for i32` and `TryFrom<i32> for Self` on fieldless enums |
 
  Multiple conversion attributes can be specified for a single type:
 
@@ -94,15 +111,42 @@ mod util;
  | Attribute | Description |
  |-----------|-------------|
  | `#[convert(rename = "new_name")]` | Maps field to different name in target |
+ | `#[convert(name = "x")]` | Maps a positional field to a named field in target |
+ | `#[convert(index = 0)]` | Maps a named field to a positional field in target |
  | `#[convert(skip)]` | Excludes field from conversion |
  | `#[convert(default)]` | Uses `Default::default()` for this field |
  | `#[convert(unwrap)]` | Unwraps `Option` (`try_from` fails if `None`) |
+ | `#[convert(some)]` | Wraps the value in `Some` (the reverse conversion unwraps it) |
  | `#[convert(unwrap_or_default)]` | Automatically calls unwrap_or_default on `Option` value before converting it |
+ | `#[convert(enum_i32)]` | Converts between `i32` and an enum with `repr(i32)` conversions (`try_into`/`try_from` for the `i32` to enum direction) |
+ | `#[convert(flatten(fields(a, b = "c")))]` | Spreads fields of a nested struct into the target (regroups them in `from`) |
+ | `#[convert(from(getter = "name"))]` | Reads the field through `source.name()` in from conversions (`from(path = "T", getters)` does this for all fields) |
+ | `#[convert(borrow)]` | Converts from `&field` through `From<&T>` instead of an owned or cloned value (`clone` overrides it per conversion) |
+ | `#[convert(as_ref)]` / `#[convert(as_deref)]` | Borrows the field with `as_ref()` / `as_deref()`, for views like `into(path = "View<'a>", by_ref)` |
+ | `#[convert(source = "address?.city")]` | Reads the field from a nested source member, unwrapping `?` members |
  | `#[convert(with_func = "func_name")]` | Uses custom conversion function |
 
+ Variants accept `rename` and `skip` as well, plus a variant-level `with_func`
+ that builds the whole target value from the variant's non-`default` fields (add `by_ref` to
+ receive them by reference). `#[convert(fields = ["x", "y"])]` names the fields of a
+ tuple variant so that it converts to or from a struct variant. `discard_fields` drops
+ the source payload to produce a unit variant, and `default_fields(x, y = "expr")`
+ fills the target payload when converting from a unit variant (`default_fields(0, 1 = "expr")`
+ for a tuple variant).
+ With `default` on the conversion, tuple targets are filled up to a declared
+ `arity = N`, using `fill(index = i, value = "expr")` or `Default::default()` for the
+ positions no field maps to. Variants take their own `arity` and `fill`.
+
+ With `repr`, variant values come from explicit discriminants, and can be overridden
+ with `#[convert(value = 3)]`. With `string`, variant names follow `rename`, then the
+ optional `string(case = "snake_case")` convention, and `#[convert(alias = "name")]` adds
+ extra spellings accepted when parsing.
+
  ### Custom Conversion Functions
 
- Functions specified with `with_func` must accept a reference to the source type:
+ Functions specified with `with_func` must accept a reference to the source type
+ (inside an enum variant, a reference to each of the variant's fields instead, leaving out
+ `default` fields, which the source doesn't have):
 
  ```rust
  use derive_into::Convert;
@@ -269,5 +313,42 @@ mod tests {
         t.pass("tests/cases/test_enum_conversions.rs");
         t.pass("tests/cases/test_struct_conversions.rs");
         t.pass("tests/cases/test_field_attributes.rs");
+        t.pass("tests/cases/test_variant_conversions.rs");
+        t.pass("tests/cases/test_enum_mappings.rs");
+        t.pass("tests/cases/test_tagged_conversions.rs");
+        t.pass("tests/cases/test_nested_fields.rs");
+        t.pass("tests/cases/test_shape_conversions.rs");
+        t.pass("tests/cases/test_construction.rs");
+        t.pass("tests/cases/test_borrowed_conversions.rs");
+        t.pass("tests/cases/test_bidirectional_conversions.rs");
+        t.pass("tests/cases/test_multi_target_conversions.rs");
+        t.pass("tests/cases/test_naming_conventions.rs");
+        t.pass("tests/cases/test_serde_names.rs");
+        t.pass("tests/cases/test_field_mapping.rs");
+        t.pass("tests/cases/test_exhaustive_conversions.rs");
+        t.pass("tests/cases/test_cfg_conversions.rs");
+        t.compile_fail("tests/cases/fail/tuple_with_path.rs");
+        t.compile_fail("tests/cases/fail/unit_from_struct.rs");
+        t.compile_fail("tests/cases/fail/unit_from_variant.rs");
+        t.compile_fail("tests/cases/fail/unit_tuple.rs");
+        t.compile_fail("tests/cases/fail/constructor_without_func.rs");
+        t.compile_fail("tests/cases/fail/constructor_both_kinds.rs");
+        t.compile_fail("tests/cases/fail/constructor_args_path.rs");
+        t.compile_fail("tests/cases/fail/build_without_builder.rs");
+        t.compile_fail("tests/cases/fail/builder_with_constructor.rs");
+        t.compile_fail("tests/cases/fail/paths_invalid.rs");
+        t.compile_fail("tests/cases/fail/paths_with_path.rs");
+        t.compile_fail("tests/cases/fail/rename_all_kebab.rs");
+        t.compile_fail("tests/cases/fail/add_prefix_invalid.rs");
+        t.compile_fail("tests/cases/fail/ignore_without_exhaustive.rs");
+        t.compile_fail("tests/cases/fail/ignore_path.rs");
+        t.compile_fail("tests/cases/fail/tag_from.rs");
+        t.compile_fail("tests/cases/fail/fields_not_ident.rs");
+        t.compile_fail("tests/cases/fail/name_not_ident.rs");
+        t.compile_fail("tests/cases/fail/rename_not_ident.rs");
+        t.compile_fail("tests/cases/fail/enum_i32_from.rs");
+        t.compile_fail("tests/cases/fail/enum_getters.rs");
+        t.compile_fail("tests/cases/fail/default_fields_positions.rs");
+        t.compile_fail("tests/cases/fail/default_fields_mixed.rs");
     }
 }
@@ -1,12 +1,16 @@
-use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::{DataStruct, spanned::Spanned};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{DataStruct, Type, spanned::Spanned};
 
 use crate::{
     attribute_parsing::{
-        conversion_field::extract_convertible_fields, conversion_meta::ConversionMeta,
+        conversion_field::{
+            ConvertibleField, FieldConversionMethod, FieldIdentifier, Flatten,
+            extract_convertible_fields, other_side_named,
+        },
+        conversion_meta::{Builder, Constructor, ConversionMeta, OtherKind, without_generics},
     },
-    derive_into::build_field_conversions,
+    derive_into::{FieldSource, build_field_conversions, fill_positions},
 };
 
 pub(super) fn implement_all_struct_conversions(
@@ -16,26 +20,79 @@ pub(super) fn implement_all_struct_conversions(
     let named_struct = match &data_struct.fields {
         syn::Fields::Named(_) => true,
         syn::Fields::Unnamed(_) => false,
-        syn::Fields::Unit => panic!("Unit structs are not supported for conversion"),
+        syn::Fields::Unit => {
+            let conversion_impls = conversions
+                .into_iter()
+                .map(|conversion| {
+                    let conversion_impl =
+                        implement_unit_conversion(conversion.clone(), data_struct)?;
+                    Ok(conversion.gated(conversion_impl))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            return Ok(quote! {
+                #(#conversion_impls)*
+            });
+        }
     };
 
     let conversion_impls: Vec<_> = conversions
         .into_iter()
-        .map(|conversion| {
+        .map(|mut conversion| {
+            let is_from = conversion.method.is_from();
+            let mut fields = extract_convertible_fields(
+                &data_struct.fields,
+                conversion.method,
+                &conversion.other_type(),
+                &conversion.naming,
+                &conversion.mapping,
+            )?;
+
+            if conversion.getters {
+                use_getters(&conversion, &mut fields)?;
+            }
+
+            if conversion.other_kind != OtherKind::Path {
+                let other_type = fields_as_tuple_or_array(&conversion, &mut fields, data_struct)?;
+                if is_from {
+                    conversion.source_name = other_type;
+                } else {
+                    conversion.target_name = other_type;
+                }
+            }
+
+            let other_named = other_side_named(
+                &mut fields,
+                is_from,
+                named_struct,
+                data_struct.fields.span(),
+            )?;
+            let target_named = if is_from { named_struct } else { other_named };
+
+            // Array elements can't be moved out by index, so the source array is destructured
+            let (field_source, prelude) = if is_from && conversion.other_kind == OtherKind::Array {
+                let bindings = fields.iter().map(|field| field.source_name.as_named());
+                (
+                    FieldSource::bound(&fields, conversion.by_ref),
+                    quote! { let [#(#bindings),*] = source; },
+                )
+            } else if conversion.exhaustive {
+                (
+                    FieldSource::prefixed(conversion.by_ref),
+                    exhaustive_check(&conversion, &fields)?,
+                )
+            } else {
+                (FieldSource::prefixed(conversion.by_ref), quote! {})
+            };
+
             implement_struct_conversion(
                 conversion.clone(),
-                named_struct,
-                build_field_conversions(
-                    &conversion,
-                    named_struct,
-                    true,
-                    &extract_convertible_fields(
-                        &data_struct.fields,
-                        conversion.method,
-                        &conversion.other_type(),
-                    )?,
-                )?,
+                target_named,
+                data_struct,
+                prelude,
+                field_source,
+                &fields,
             )
+            .map(|conversion_impl| conversion.gated(conversion_impl))
         })
         .collect::<Result<_, _>>()?;
 
@@ -44,56 +101,607 @@ pub(super) fn implement_all_struct_conversions(
     })
 }
 
+// A pattern naming every source field that is mapped or ignored, without `..`, so that the
+// conversion stops compiling when the source gains a field. Fields built by `with_func`,
+// getters or defaults don't name a source field, so what they read goes in `ignore`.
+fn exhaustive_check(
+    conversion: &ConversionMeta,
+    fields: &[ConvertibleField],
+) -> syn::Result<TokenStream2> {
+    if !conversion.method.is_from() || conversion.other_kind != OtherKind::Path {
+        return Err(syn::Error::new(
+            conversion.source_name.span(),
+            "`exhaustive` only applies to from conversions with a `path`",
+        ));
+    }
+
+    let mut names: Vec<TokenStream2> = Vec::new();
+    let mut add = |name: TokenStream2| {
+        if !names
+            .iter()
+            .any(|known| known.to_string() == name.to_string())
+        {
+            names.push(name);
+        }
+    };
+    for field in fields {
+        match &field.flatten {
+            Some(Flatten::Regroup(_, pairs)) => {
+                pairs.iter().for_each(|(_, flat)| add(quote! { #flat }));
+            }
+            _ if field.default || field.conversion_func.is_some() || field.getter.is_some() => {}
+            _ => {
+                let source_name = &field.source_name;
+                add(quote! { #source_name });
+            }
+        }
+    }
+    conversion
+        .ignore
+        .iter()
+        .for_each(|ignored| add(quote! { #ignored }));
+
+    // Spanned at the source path, so that the compiler points there for missing fields
+    let source_path = without_generics(&conversion.source_name);
+    Ok(quote_spanned! { source_path.span() =>
+        let #source_path { #(#names: _),* } = &source;
+    })
+}
+
+// Reads every field that isn't read some other way through a getter named after the source
+// field
+fn use_getters(conversion: &ConversionMeta, fields: &mut [ConvertibleField]) -> syn::Result<()> {
+    if !conversion.method.is_from() || conversion.other_kind != OtherKind::Path {
+        return Err(syn::Error::new(
+            conversion.source_name.span(),
+            "`getters` only applies to from conversions with a `path`",
+        ));
+    }
+    for field in fields.iter_mut() {
+        if field.getter.is_some()
+            || field.default
+            || field.conversion_func.is_some()
+            || field.flatten.is_some()
+        {
+            continue;
+        }
+        let FieldIdentifier::Named(name) = &field.source_name else {
+            return Err(syn::Error::new(
+                field.span,
+                "`getters` needs named source fields",
+            ));
+        };
+        field.getter = Some(name.clone());
+    }
+    Ok(())
+}
+
+// Maps the fields, in order, to the positions of a tuple or array made of their types, and
+// returns that type
+fn fields_as_tuple_or_array(
+    conversion: &ConversionMeta,
+    fields: &mut [ConvertibleField],
+    data_struct: &DataStruct,
+) -> syn::Result<Type> {
+    let is_from = conversion.method.is_from();
+    for (position, field) in fields.iter_mut().enumerate() {
+        *field.other_name_mut(is_from) = FieldIdentifier::Unnamed(position);
+    }
+
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    if conversion.other_kind == OtherKind::Tuple {
+        return Ok(syn::parse_quote! { (#(#types,)*) });
+    }
+
+    let Some(element_type) = types.first() else {
+        return Err(syn::Error::new(
+            data_struct.fields.span(),
+            "Array conversions require at least one field",
+        ));
+    };
+    if let Some(field) = fields.iter().find(|field| field.ty != **element_type) {
+        return Err(syn::Error::new(
+            field.span,
+            "Array conversions require all fields to have the same type",
+        ));
+    }
+    let len = types.len();
+    Ok(syn::parse_quote! { [#element_type; #len] })
+}
+
+// Named fields with conversion functions go first, so that they can still borrow `source`
+// before the other fields are moved out of it. Positional fields follow the target's order.
+pub(super) fn sorted_for_struct(
+    target_named: bool,
+    mut fields: Vec<ConvertibleField>,
+) -> Vec<ConvertibleField> {
+    if target_named {
+        fields.sort_by_key(|field| field.conversion_func.is_none());
+    } else {
+        fields.sort_by_key(|field| field.target_name.index());
+    }
+    fields
+}
+
+// Constructors and builders replace the struct literal of into conversions, so the options
+// shaping that literal don't apply
+fn check_built_target(meta: &ConversionMeta, span: Span, what: &str) -> syn::Result<()> {
+    if meta.method.is_from() {
+        return Err(syn::Error::new(
+            span,
+            format!("{} only apply to into conversions", what),
+        ));
+    }
+    if meta.other_kind != OtherKind::Path || meta.default_allowed || !meta.fill.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "{} can't be combined with `tuple`, `array`, `default` or `fill`",
+                what
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Converts the fields listed in `args`, or all of them, and passes them to the constructor in
+// order. Fields are converted up front, with `with_func` fields first as in struct literals.
+fn constructor_call(
+    meta: &ConversionMeta,
+    constructor: &Constructor,
+    field_source: FieldSource,
+    fields: &[ConvertibleField],
+) -> syn::Result<TokenStream2> {
+    let Constructor {
+        func,
+        fallible,
+        args,
+    } = constructor;
+    let span = func.span();
+
+    check_built_target(meta, span, "Constructors")?;
+    if *fallible && !meta.method.is_falliable() {
+        return Err(syn::Error::new(
+            span,
+            "`try_constructor` requires a `try_into` conversion",
+        ));
+    }
+
+    let args: Vec<ConvertibleField> = match args {
+        Some(args) => args
+            .iter()
+            .map(|arg| {
+                fields
+                    .iter()
+                    .find(|field| {
+                        matches!(&field.source_name, FieldIdentifier::Named(name) if name == arg)
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            arg.span(),
+                            format!("No field named {} to pass to the constructor", arg),
+                        )
+                    })
+            })
+            .collect::<syn::Result<_>>()?,
+        None => fields.to_vec(),
+    };
+    if let Some(field) = args.iter().find(|field| field.flatten.is_some()) {
+        return Err(syn::Error::new(
+            field.span,
+            "flatten is not supported with constructors",
+        ));
+    }
+
+    let bindings: Vec<_> = args
+        .iter()
+        .map(|field| field.source_name.as_named())
+        .collect();
+    let sorted = sorted_for_struct(true, args.clone());
+    let sorted_bindings = sorted.iter().map(|field| field.source_name.as_named());
+    let conversions = build_field_conversions(meta, false, field_source, &sorted)?;
+    let let_args = quote! {
+        let (#(#sorted_bindings,)*) = (#(#conversions)*);
+    };
+
+    let ConversionMeta {
+        source_name,
+        target_name,
+        ..
+    } = meta;
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    Ok(if *fallible {
+        quote! {{
+            #let_args
+            #func(#(#bindings),*).map_err(|e|
+                #error_creator("Failed trying to convert {} to {}: {:?}",
+                    stringify!(#source_name),
+                    stringify!(#target_name),
+                    e,
+                )
+            )?
+        }}
+    } else {
+        quote! {{
+            #let_args
+            #func(#(#bindings),*)
+        }}
+    })
+}
+
+// Calls a setter named after the target field for each field, then finishes the builder.
+// Option fields only call their setter when set. In try_into conversions the builder's
+// `build` returns a `Result`, whose error becomes the conversion error.
+fn builder_call(
+    meta: &ConversionMeta,
+    builder: &Builder,
+    field_source: FieldSource,
+    fields: &[ConvertibleField],
+) -> syn::Result<TokenStream2> {
+    let Builder { func, build } = builder;
+    check_built_target(meta, func.span(), "Builders")?;
+
+    let setters = sorted_for_struct(true, fields.to_vec())
+        .into_iter()
+        .map(|field| {
+            let FieldIdentifier::Named(setter) = field.target_name.clone() else {
+                return Err(syn::Error::new(
+                    field.span,
+                    "Builder setters need a named target field",
+                ));
+            };
+            if field.flatten.is_some() {
+                return Err(syn::Error::new(
+                    field.span,
+                    "flatten is not supported with builders",
+                ));
+            }
+
+            let optional = matches!(field.method, FieldConversionMethod::Option)
+                && field.conversion_func.is_none()
+                && field.source_path.is_empty()
+                && !field.default;
+            if !optional {
+                let conversion = build_field_conversions(
+                    meta,
+                    false,
+                    field_source,
+                    std::slice::from_ref(&field),
+                )?;
+                return Ok(quote! { builder = builder.#setter(#(#conversion)*); });
+            }
+
+            // The set value is bound under the field's name and converted like a plain field
+            let value = field_source.access(&field.source_name);
+            let binding = field.source_name.as_named();
+            let inner = ConvertibleField {
+                method: FieldConversionMethod::Plain,
+                ..field
+            };
+            let conversion = build_field_conversions(
+                meta,
+                false,
+                FieldSource::Bound(std::slice::from_ref(&inner)),
+                std::slice::from_ref(&inner),
+            )?;
+            Ok(quote! {
+                if let Some(#binding) = #value {
+                    builder = builder.#setter(#(#conversion)*);
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ConversionMeta {
+        source_name,
+        target_name,
+        method,
+        ..
+    } = meta;
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    let build = if method.is_falliable() {
+        quote! {
+            builder.#build().map_err(|e|
+                #error_creator("Failed trying to convert {} to {}: {:?}",
+                    stringify!(#source_name),
+                    stringify!(#target_name),
+                    e,
+                )
+            )?
+        }
+    } else {
+        quote! { builder.#build() }
+    };
+
+    Ok(quote! {{
+        let mut builder = #func();
+        #(#setters)*
+        #build
+    }})
+}
+
 fn implement_struct_conversion(
     meta: ConversionMeta,
-    named_struct: bool,
-    fields: Vec<TokenStream2>,
+    target_named: bool,
+    data_struct: &DataStruct,
+    prelude: TokenStream2,
+    field_source: FieldSource,
+    fields: &[ConvertibleField],
 ) -> syn::Result<TokenStream2> {
+    let constructed = meta
+        .constructor
+        .as_ref()
+        .map(|constructor| constructor_call(&meta, constructor, field_source, fields))
+        .or_else(|| {
+            meta.builder
+                .as_ref()
+                .map(|builder| builder_call(&meta, builder, field_source, fields))
+        })
+        .transpose()?;
+    let fields = &sorted_for_struct(target_named, fields.to_vec());
+    let conversions = build_field_conversions(&meta, target_named, field_source, fields)?;
+    let source_type = meta.source_type();
+    let impl_generics = meta.impl_generics();
+    let target_path = without_generics(&meta.target_name);
     let ConversionMeta {
         source_name,
         target_name,
         method,
+        other_kind,
         default_allowed,
+        arity,
+        fill,
+        tag,
+        variant,
+        constructor: _,
+        builder: _,
+        getters: _,
+        by_ref: _,
+        naming: _,
+        mapping: _,
+        exhaustive: _,
+        ignore: _,
+        cfg: _,
     } = meta;
 
-    if !named_struct && default_allowed {
+    if let Some(tag) = tag {
+        return Err(syn::Error::new(
+            tag.span(),
+            "Tagged conversions are only supported for enums",
+        ));
+    }
+
+    if let Some(variant) = variant {
+        return Err(syn::Error::new(
+            variant.span(),
+            "`variant` conversions are only supported for unit structs",
+        ));
+    }
+
+    if (arity.is_some() || !fill.is_empty()) && (target_named || !default_allowed) {
+        return Err(syn::Error::new(
+            source_name.span(),
+            "`arity` and `fill` require `default` and an unnamed target",
+        ));
+    }
+
+    if other_kind != OtherKind::Path && default_allowed {
+        return Err(syn::Error::new(
+            source_name.span(),
+            "Default values are not supported for tuple and array conversions",
+        ));
+    }
+
+    // In from conversions the target is this struct, so its arity is known
+    if method.is_from() && arity.is_some() {
         return Err(syn::Error::new(
             source_name.span(),
-            "Default values are not supported for unnamed structs",
+            "`arity` only applies to into conversions",
         ));
     }
+    let arity = if method.is_from() {
+        Some(data_struct.fields.len())
+    } else {
+        arity
+    };
+
+    let conversions = if !target_named && default_allowed {
+        let Some(arity) = arity else {
+            return Err(syn::Error::new(
+                source_name.span(),
+                "Default values for unnamed targets require `arity`",
+            ));
+        };
+        fill_positions(fields, conversions, arity, &fill, source_name.span())?
+    } else {
+        conversions
+    };
 
-    let default_fields = if default_allowed {
+    let default_fields = if default_allowed && target_named {
         quote! { ..Default::default() }
     } else {
         quote! {}
     };
 
-    let inner = if named_struct {
-        quote! { #target_name { #(#fields)* #default_fields } }
+    let inner = match (constructed, other_kind) {
+        (Some(constructed), _) => constructed,
+        (None, OtherKind::Tuple) if !method.is_from() => quote! { (#(#conversions)*) },
+        (None, OtherKind::Array) if !method.is_from() => quote! { [#(#conversions)*] },
+        _ if target_named => quote! { #target_path { #(#conversions)* #default_fields } },
+        _ => quote! { #target_path(#(#conversions)*) },
+    };
+
+    let error_type = if cfg!(feature = "anyhow") {
+        quote! { anyhow::Error }
     } else {
-        quote! { #target_name(#(#fields)* #default_fields) }
+        quote! { String }
     };
 
+    Ok(if method.is_falliable() {
+        quote! {
+            impl #impl_generics TryFrom<#source_type> for #target_name {
+                type Error = #error_type;
+                fn try_from(source: #source_type) -> Result<#target_name, Self::Error> {
+                    #prelude
+                    Ok(#inner)
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics From<#source_type> for #target_name {
+                fn from(source: #source_type) -> #target_name {
+                    #prelude
+                    #inner
+                }
+            }
+        }
+    })
+}
+
+/// Converts a unit struct to a unit struct, a unit variant given by `variant`, or with
+/// `default` a struct whose fields are all defaulted. Converting from another type drops
+/// its value, except for `variant`, where other variants fail to convert.
+fn implement_unit_conversion(
+    meta: ConversionMeta,
+    data_struct: &DataStruct,
+) -> syn::Result<TokenStream2> {
+    let source_type = meta.source_type();
+    let impl_generics = meta.impl_generics();
+    let source_path = without_generics(&meta.source_name);
+    let target_path = without_generics(&meta.target_name);
+    let ConversionMeta {
+        source_name,
+        target_name,
+        method,
+        other_kind,
+        default_allowed,
+        arity,
+        fill,
+        tag,
+        variant,
+        constructor,
+        builder,
+        getters: _,
+        by_ref: _,
+        naming: _,
+        mapping: _,
+        exhaustive: _,
+        ignore: _,
+        cfg: _,
+    } = meta;
+    let span = data_struct.struct_token.span;
+
+    if let Some(builder) = builder {
+        return Err(syn::Error::new(
+            builder.func.span(),
+            "Builders are not supported for unit structs",
+        ));
+    }
+
+    if let Some(constructor) = constructor {
+        return Err(syn::Error::new(
+            constructor.func.span(),
+            "Constructors are not supported for unit structs",
+        ));
+    }
+
+    if other_kind != OtherKind::Path {
+        return Err(syn::Error::new(
+            span,
+            "Unit structs only convert to and from types given by `path`",
+        ));
+    }
+    if let Some(tag) = tag {
+        return Err(syn::Error::new(
+            tag.span(),
+            "Tagged conversions are only supported for enums",
+        ));
+    }
+    if arity.is_some() || !fill.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "`arity` and `fill` are not supported for unit structs",
+        ));
+    }
+    if default_allowed && (method.is_from() || variant.is_some()) {
+        return Err(syn::Error::new(
+            span,
+            "`default` on unit structs only applies to into conversions without `variant`",
+        ));
+    }
+
     let error_type = if cfg!(feature = "anyhow") {
         quote! { anyhow::Error }
     } else {
         quote! { String }
     };
 
+    let error_creator = if cfg!(feature = "anyhow") {
+        quote!(anyhow::anyhow!)
+    } else {
+        quote!(format!)
+    };
+
+    let inner = match (method.is_from(), variant) {
+        (false, Some(variant)) => quote! { #target_path::#variant },
+        (false, None) if default_allowed => quote! { Default::default() },
+        (false, None) => quote! { #target_path },
+        (true, Some(variant)) => {
+            if !method.is_falliable() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "Converting from a single variant can fail, use `try_from`",
+                ));
+            }
+            return Ok(quote! {
+                impl #impl_generics TryFrom<#source_type> for #target_name {
+                    type Error = #error_type;
+                    fn try_from(source: #source_type) -> Result<#target_name, Self::Error> {
+                        match source {
+                            #source_path::#variant => Ok(#target_path),
+                            _ => Err(#error_creator(
+                                "Failed trying to convert {} to {}: expected {}",
+                                stringify!(#source_name),
+                                stringify!(#target_name),
+                                stringify!(#variant),
+                            )),
+                        }
+                    }
+                }
+            });
+        }
+        // Only a unit struct matches the empty pattern, spanned so the error points at `path`
+        (true, None) => quote_spanned! { source_path.span() => {
+            let #source_path {} = _source;
+            #target_path
+        }},
+    };
+
     Ok(if method.is_falliable() {
         quote! {
-            impl TryFrom<#source_name> for #target_name {
+            impl #impl_generics TryFrom<#source_type> for #target_name {
                 type Error = #error_type;
-                fn try_from(source: #source_name) -> Result<#target_name, Self::Error> {
+                fn try_from(_source: #source_type) -> Result<#target_name, Self::Error> {
                     Ok(#inner)
                 }
             }
         }
     } else {
         quote! {
-            impl From<#source_name> for #target_name {
-                fn from(source: #source_name) -> #target_name {
+            impl #impl_generics From<#source_type> for #target_name {
+                fn from(_source: #source_type) -> #target_name {
                     #inner
                 }
             }
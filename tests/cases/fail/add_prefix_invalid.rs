@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+struct Target {
+    user_name: String,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", add_prefix = "1"))]
+struct Source {
+    user_name: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", build = finish))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
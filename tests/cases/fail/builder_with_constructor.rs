@@ -0,0 +1,19 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+impl Target {
+    fn new(x: u32) -> Self {
+        Target { x }
+    }
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", builder = Target::new, constructor = Target::new))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
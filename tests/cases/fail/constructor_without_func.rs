@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", args(x)))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use derive_into::Convert;
+
+enum Target {
+    Queued(u8, String),
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target"))]
+enum Source {
+    #[convert(default_fields(0, note))]
+    Queued,
+}
+
+fn main() {}
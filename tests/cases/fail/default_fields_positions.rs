@@ -0,0 +1,14 @@
+use derive_into::Convert;
+
+enum Target {
+    Queued(u8, String),
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target"))]
+enum Source {
+    #[convert(default_fields(1, 0))]
+    Queued,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+enum Target {
+    Idle,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "Target", getters))]
+enum Source {
+    Idle,
+}
+
+fn main() {}
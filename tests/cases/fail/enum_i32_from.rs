@@ -0,0 +1,21 @@
+use derive_into::Convert;
+
+#[derive(Convert)]
+#[convert(repr(i32))]
+enum Status {
+    Unknown,
+    Active,
+}
+
+struct WireAccount {
+    status: i32,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "WireAccount"))]
+struct Account {
+    #[convert(enum_i32)]
+    status: Status,
+}
+
+fn main() {}
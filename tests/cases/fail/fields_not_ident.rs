@@ -0,0 +1,14 @@
+use derive_into::Convert;
+
+enum Target {
+    Click { x: u64, y: u64 },
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target"))]
+enum Source {
+    #[convert(fields = ["not an ident", "y"])]
+    Click(u64, u64),
+}
+
+fn main() {}
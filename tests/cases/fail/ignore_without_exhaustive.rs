@@ -0,0 +1,14 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "Target", ignore(y)))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
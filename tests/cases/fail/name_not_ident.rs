@@ -0,0 +1,11 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target"))]
+struct Source(#[convert(name = "x-y")] u32);
+
+fn main() {}
@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(paths = ["Target", "not a path"]))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+struct Other {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", paths = ["Other"]))]
+struct Source {
+    x: u32,
+}
+
+fn main() {}
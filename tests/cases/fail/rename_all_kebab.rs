@@ -0,0 +1,13 @@
+use derive_into::Convert;
+
+struct Target {
+    user_name: String,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target", rename_all = "kebab-case"))]
+struct Source {
+    user_name: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use derive_into::Convert;
+
+struct Target {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(into(path = "Target"))]
+struct Source {
+    #[convert(into(rename = "x y"))]
+    x: u32,
+}
+
+fn main() {}
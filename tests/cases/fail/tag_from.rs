@@ -0,0 +1,15 @@
+use derive_into::Convert;
+
+#[derive(Default)]
+struct Row {
+    kind: String,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "Row", tag = "kind"))]
+enum Kind {
+    Open,
+    Closed,
+}
+
+fn main() {}
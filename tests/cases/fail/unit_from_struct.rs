@@ -0,0 +1,11 @@
+use derive_into::Convert;
+
+struct Payload {
+    x: u32,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "Payload"))]
+struct Marker;
+
+fn main() {}
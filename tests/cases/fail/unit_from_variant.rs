@@ -0,0 +1,12 @@
+use derive_into::Convert;
+
+enum State {
+    Idle,
+    Running,
+}
+
+#[derive(Convert)]
+#[convert(from(path = "State", variant = "Idle"))]
+struct Idle;
+
+fn main() {}
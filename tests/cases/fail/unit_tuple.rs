@@ -0,0 +1,7 @@
+use derive_into::Convert;
+
+#[derive(Convert)]
+#[convert(into(tuple))]
+struct Marker;
+
+fn main() {}
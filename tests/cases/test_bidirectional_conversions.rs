@@ -0,0 +1,88 @@
+use derive_into::Convert;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Cents(u64);
+
+impl From<u64> for Cents {
+    fn from(cents: u64) -> Self {
+        Cents(cents)
+    }
+}
+
+impl From<Cents> for u64 {
+    fn from(cents: Cents) -> Self {
+        cents.0
+    }
+}
+
+// =================== both ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(both(path = "InvoiceRow"))]
+struct Invoice {
+    #[convert(rename = "invoice_id")]
+    id: u32,
+    #[convert(both(path = "InvoiceRow", rename = "amount_cents"))]
+    amount: u64,
+    // `Some` in the row, unwrapped when reading it back
+    #[convert(some)]
+    customer: String,
+    #[convert(unwrap)]
+    paid_at: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct InvoiceRow {
+    invoice_id: u32,
+    amount_cents: Cents,
+    customer: Option<String>,
+    paid_at: String,
+}
+
+// =================== try_both ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(try_both(path = "Status"))]
+enum StatusCode {
+    #[convert(try_both(rename = "Active"))]
+    Ok,
+    Suspended {
+        #[convert(rename = "days")]
+        duration: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Status {
+    Active,
+    Suspended { days: u32 },
+}
+
+fn main() {
+    let invoice = Invoice {
+        id: 7,
+        amount: 1250,
+        customer: "ada".to_string(),
+        paid_at: Some("2024-01-01".to_string()),
+    };
+
+    let row: InvoiceRow = invoice.clone().into();
+    assert_eq!(
+        row,
+        InvoiceRow {
+            invoice_id: 7,
+            amount_cents: Cents(1250),
+            customer: Some("ada".to_string()),
+            paid_at: "2024-01-01".to_string(),
+        }
+    );
+    assert_eq!(Invoice::from(row), invoice);
+
+    let status: Status = StatusCode::Ok.try_into().unwrap();
+    assert_eq!(status, Status::Active);
+    let status: Status = StatusCode::Suspended { duration: 3 }.try_into().unwrap();
+    assert_eq!(status, Status::Suspended { days: 3 });
+    assert_eq!(
+        StatusCode::try_from(Status::Suspended { days: 300 }).map_err(|_| ()),
+        Err(())
+    );
+    assert_eq!(StatusCode::try_from(Status::Active), Ok(StatusCode::Ok));
+}
@@ -0,0 +1,200 @@
+use derive_into::Convert;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Tag(String);
+
+impl From<&str> for Tag {
+    fn from(tag: &str) -> Self {
+        Tag(tag.to_string())
+    }
+}
+
+impl From<String> for Tag {
+    fn from(tag: String) -> Self {
+        Tag(tag)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Summary(usize);
+
+impl From<&Vec<String>> for Summary {
+    fn from(items: &Vec<String>) -> Self {
+        Summary(items.len())
+    }
+}
+
+// =================== Structs by reference ===================
+fn initials(source: &Order) -> String {
+    source.customer.chars().take(1).collect()
+}
+
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "OrderResponse", by_ref))]
+struct Order {
+    id: u32,
+    customer: String,
+    #[convert(with_func = "initials")]
+    initials: String,
+    tags: Vec<String>,
+    #[convert(borrow)]
+    items: Vec<String>,
+    note: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct OrderResponse {
+    id: u64,
+    customer: String,
+    initials: String,
+    tags: Vec<Tag>,
+    items: Summary,
+    note: Option<String>,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "OrderResponse", by_ref))]
+struct OrderCopy {
+    customer: String,
+    #[convert(rename = "tags")]
+    tag_list: Vec<Tag>,
+    note: Option<String>,
+}
+
+// =================== Enums by reference ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "EventDto", by_ref))]
+enum Event {
+    Created { id: u32, name: String },
+    Renamed(u32, String),
+    Deleted,
+}
+
+#[derive(Debug, PartialEq)]
+enum EventDto {
+    Created { id: u64, name: Tag },
+    Renamed(u64, Tag),
+    Deleted,
+}
+
+// =================== Borrowed views ===================
+#[derive(Convert, Debug)]
+#[convert(into(path = "UserView<'a>", by_ref))]
+#[convert(into(path = "ProfileView<'a>", by_ref))]
+struct User {
+    id: u32,
+    #[convert(as_ref)]
+    name: String,
+    #[convert(as_ref)]
+    roles: Vec<String>,
+    #[convert(into(path = "UserView<'a>", as_deref))]
+    #[convert(into(path = "ProfileView<'a>", skip))]
+    nickname: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserView<'a> {
+    id: u32,
+    name: &'a str,
+    roles: &'a [String],
+    nickname: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ProfileView<'a> {
+    id: u64,
+    name: &'a str,
+    roles: &'a [String],
+}
+
+#[derive(Convert, Debug)]
+#[convert(into(path = "ShapeView<'s>", by_ref))]
+enum Shape {
+    Named {
+        #[convert(as_ref)]
+        label: String,
+    },
+    Points(#[convert(as_ref)] Vec<(i32, i32)>),
+}
+
+#[derive(Debug, PartialEq)]
+enum ShapeView<'s> {
+    Named { label: &'s str },
+    Points(&'s [(i32, i32)]),
+}
+
+fn main() {
+    let order = Order {
+        id: 1,
+        customer: "ada".to_string(),
+        initials: String::new(),
+        tags: vec!["new".to_string()],
+        items: vec!["tea".to_string(), "cake".to_string()],
+        note: Some("fragile".to_string()),
+    };
+
+    let response: OrderResponse = (&order).into();
+    assert_eq!(
+        response,
+        OrderResponse {
+            id: 1,
+            customer: "ada".to_string(),
+            initials: "a".to_string(),
+            tags: vec![Tag("new".to_string())],
+            items: Summary(2),
+            note: Some("fragile".to_string()),
+        }
+    );
+    // The order is still usable
+    assert_eq!(order.customer, "ada");
+
+    let copy = OrderCopy::from(&response);
+    assert_eq!(copy.customer, response.customer);
+    assert_eq!(copy.tag_list, response.tags);
+    assert_eq!(copy.note, response.note);
+
+    let event = Event::Created {
+        id: 3,
+        name: "draft".to_string(),
+    };
+    let dto: EventDto = (&event).into();
+    assert_eq!(
+        dto,
+        EventDto::Created {
+            id: 3,
+            name: Tag("draft".to_string())
+        }
+    );
+    let dto: EventDto = (&Event::Renamed(4, "final".to_string())).into();
+    assert_eq!(dto, EventDto::Renamed(4, Tag("final".to_string())));
+    let dto: EventDto = (&Event::Deleted).into();
+    assert_eq!(dto, EventDto::Deleted);
+    assert!(matches!(event, Event::Created { .. }));
+
+    let user = User {
+        id: 9,
+        name: "ada".to_string(),
+        roles: vec!["admin".to_string()],
+        nickname: Some("countess".to_string()),
+    };
+    let view: UserView = (&user).into();
+    assert_eq!(
+        view,
+        UserView {
+            id: 9,
+            name: "ada",
+            roles: &["admin".to_string()],
+            nickname: Some("countess"),
+        }
+    );
+    let profile: ProfileView = (&user).into();
+    assert_eq!(profile.id, 9);
+    assert_eq!(profile.name, "ada");
+
+    let shape = Shape::Named {
+        label: "square".to_string(),
+    };
+    assert_eq!(ShapeView::from(&shape), ShapeView::Named { label: "square" });
+    let shape = Shape::Points(vec![(0, 0), (1, 1)]);
+    assert_eq!(ShapeView::from(&shape), ShapeView::Points(&[(0, 0), (1, 1)]));
+}
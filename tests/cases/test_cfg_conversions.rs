@@ -0,0 +1,76 @@
+use derive_into::Convert;
+
+// `cfg(all())` always holds and `cfg(any())` never does, standing in for enabled and
+// disabled features. The targets gated off don't exist, so their impls must not be
+// generated.
+
+// =================== cfg on a struct conversion ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "LegacyPoint", cfg(any())))]
+#[convert(try_from(path = "LegacyPoint", cfg(any())))]
+#[convert(into(path = "Point2", cfg(all())))]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq)]
+struct Point2 {
+    x: i32,
+    y: i32,
+}
+
+// =================== cfg on an enum conversion ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "LegacyEvent", cfg(any())))]
+#[convert(from(path = "EventDto", cfg(all())))]
+enum Event {
+    Login { user: String },
+    Logout,
+}
+
+#[derive(Debug, PartialEq)]
+enum EventDto {
+    Login { user: String },
+    Logout,
+}
+
+// =================== cfg on a unit struct conversion ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "LegacyAck", cfg(any())))]
+#[convert(into(path = "Ack", cfg(all())))]
+struct Done;
+
+#[derive(Debug, PartialEq)]
+struct Ack;
+
+// =================== cfg on fields ===================
+// Disabled fields are gone before the derive runs, and enabled ones convert like any other
+#[derive(Convert)]
+#[convert(into(path = "Pos"))]
+struct P2(u32, #[cfg(all())] u32, #[cfg(any())] String);
+
+#[derive(Debug, PartialEq)]
+struct Pos(u32, u32);
+
+fn main() {
+    let point: Point2 = Point { x: 1, y: 2 }.into();
+    assert_eq!(point, Point2 { x: 1, y: 2 });
+
+    let event = Event::from(EventDto::Login {
+        user: "ada".to_string(),
+    });
+    assert_eq!(
+        event,
+        Event::Login {
+            user: "ada".to_string()
+        }
+    );
+    assert_eq!(Event::from(EventDto::Logout), Event::Logout);
+
+    let ack: Ack = Done.into();
+    assert_eq!(ack, Ack);
+
+    let pos: Pos = P2(1, 2).into();
+    assert_eq!(pos, Pos(1, 2));
+}
@@ -0,0 +1,258 @@
+use derive_into::Convert;
+
+// A type from another crate, whose fields can't be set directly
+mod external {
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Email(String);
+
+    impl From<Email> for String {
+        fn from(email: Email) -> Self {
+            email.0
+        }
+    }
+
+    impl From<String> for Email {
+        fn from(email: String) -> Self {
+            Email(email)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[non_exhaustive]
+    pub struct User {
+        id: u64,
+        name: String,
+        email: Email,
+    }
+
+    impl User {
+        pub fn new(id: u64, name: String, email: Email) -> Self {
+            User { id, name, email }
+        }
+
+        pub fn id(&self) -> u64 {
+            self.id
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn email(&self) -> &Email {
+            &self.email
+        }
+
+        pub fn try_new(id: u64, name: String) -> Result<Self, String> {
+            if name.is_empty() {
+                return Err("name is empty".to_string());
+            }
+            Ok(User {
+                id,
+                name,
+                email: Email(String::new()),
+            })
+        }
+    }
+}
+
+// An SDK-style model, built through an owned builder
+mod sdk {
+    #[derive(Debug, PartialEq)]
+    #[non_exhaustive]
+    pub struct Bucket {
+        pub name: String,
+        pub region: String,
+        pub versioned: bool,
+    }
+
+    #[derive(Default)]
+    pub struct BucketBuilder {
+        name: Option<String>,
+        region: Option<String>,
+        versioned: bool,
+    }
+
+    impl Bucket {
+        pub fn builder() -> BucketBuilder {
+            BucketBuilder::default()
+        }
+    }
+
+    impl BucketBuilder {
+        pub fn name(mut self, name: String) -> Self {
+            self.name = Some(name);
+            self
+        }
+
+        pub fn region(mut self, region: String) -> Self {
+            self.region = Some(region);
+            self
+        }
+
+        pub fn versioned(mut self, versioned: bool) -> Self {
+            self.versioned = versioned;
+            self
+        }
+
+        pub fn build(self) -> Result<Bucket, String> {
+            Ok(Bucket {
+                name: self.name.ok_or("name is required")?,
+                region: self.region.unwrap_or_else(|| "us-east-1".to_string()),
+                versioned: self.versioned,
+            })
+        }
+
+        pub fn build_unchecked(self) -> Bucket {
+            Bucket {
+                name: self.name.unwrap_or_default(),
+                region: self.region.unwrap_or_default(),
+                versioned: self.versioned,
+            }
+        }
+    }
+}
+
+use external::{Email, User};
+use sdk::Bucket;
+
+// =================== Constructors ===================
+fn display_name(source: &NewUser) -> String {
+    source.name.to_uppercase()
+}
+
+#[derive(Convert, Debug, Clone)]
+#[convert(into(path = "User", constructor = "User::new", args(id, name, email)))]
+struct NewUser {
+    email: String,
+    #[convert(into(with_func = "display_name"))]
+    name: String,
+    id: u32,
+}
+
+#[derive(Convert, Debug, Clone)]
+#[convert(try_into(path = "User", try_constructor = "User::try_new", args(id, name)))]
+struct Signup {
+    name: String,
+    id: u32,
+}
+
+#[derive(Convert, Debug, Clone)]
+#[convert(into(path = "User", constructor = "User::new"))]
+struct OrderedUser {
+    id: u32,
+    name: String,
+    email: String,
+}
+
+// =================== Builders ===================
+#[derive(Convert, Debug, Clone)]
+#[convert(try_into(path = "Bucket", builder = "Bucket::builder"))]
+struct BucketSpec {
+    #[convert(rename = "name")]
+    bucket_name: String,
+    // The setter is only called when a region is set
+    region: Option<String>,
+    versioned: bool,
+}
+
+#[derive(Convert, Debug, Clone)]
+#[convert(into(path = "Bucket", builder = "Bucket::builder", build = "build_unchecked"))]
+struct QuickBucket {
+    name: String,
+}
+
+// =================== Getters ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "User", getters))]
+struct UserView {
+    id: u64,
+    name: String,
+    #[convert(rename = "email")]
+    contact: String,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(try_from(path = "User"))]
+struct UserId {
+    #[convert(try_from(getter = "id"))]
+    value: u32,
+}
+
+fn main() {
+    let new_user = NewUser {
+        email: "ada@example.com".to_string(),
+        name: "ada".to_string(),
+        id: 1,
+    };
+
+    let user: User = new_user.clone().into();
+    assert_eq!(
+        user,
+        User::new(1, "ADA".to_string(), Email::from("ada@example.com".to_string()))
+    );
+
+    let user: User = OrderedUser {
+        id: 2,
+        name: "bob".to_string(),
+        email: "bob@example.com".to_string(),
+    }
+    .into();
+    assert_eq!(
+        user,
+        User::new(2, "bob".to_string(), Email::from("bob@example.com".to_string()))
+    );
+
+    let signup = Signup {
+        name: "ada".to_string(),
+        id: 1,
+    };
+    let user: Result<User, _> = signup.try_into();
+    assert_eq!(user, User::try_new(1, "ada".to_string()));
+
+    let unnamed = Signup {
+        name: String::new(),
+        id: 3,
+    };
+    let user: Result<User, _> = unnamed.try_into();
+    assert!(user.is_err());
+
+    let bucket: Result<Bucket, _> = BucketSpec {
+        bucket_name: "logs".to_string(),
+        region: None,
+        versioned: true,
+    }
+    .try_into();
+    let bucket = bucket.unwrap();
+    assert_eq!(bucket.name, "logs");
+    assert_eq!(bucket.region, "us-east-1");
+    assert!(bucket.versioned);
+
+    let bucket: Bucket = BucketSpec {
+        bucket_name: "assets".to_string(),
+        region: Some("eu-west-1".to_string()),
+        versioned: false,
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(bucket.region, "eu-west-1");
+
+    let bucket: Bucket = QuickBucket {
+        name: "tmp".to_string(),
+    }
+    .into();
+    assert_eq!(bucket.name, "tmp");
+    assert_eq!(bucket.region, "");
+
+    let user = User::new(7, "ada".to_string(), Email::from("ada@example.com".to_string()));
+    assert_eq!(
+        UserView::from(user),
+        UserView {
+            id: 7,
+            name: "ada".to_string(),
+            contact: "ada@example.com".to_string(),
+        }
+    );
+
+    let user = User::new(8, "bob".to_string(), Email::from(String::new()));
+    assert_eq!(UserId::try_from(user), Ok(UserId { value: 8 }));
+}
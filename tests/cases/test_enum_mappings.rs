@@ -0,0 +1,98 @@
+use derive_into::Convert;
+
+// =================== Enum <-> integer discriminants ===================
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(repr(i32, u8))]
+enum Status {
+    Unknown,
+    Active = 3,
+    Suspended,
+    #[convert(value = 10)]
+    Deleted,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "WireAccount"))]
+struct Account {
+    id: u64,
+    #[convert(enum_i32)]
+    status: Status,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(try_from(path = "WireAccount"))]
+struct CheckedAccount {
+    id: u64,
+    #[convert(enum_i32)]
+    status: Status,
+}
+
+#[derive(Debug, PartialEq)]
+struct WireAccount {
+    id: u64,
+    status: i32,
+}
+
+// =================== Enum <-> string ===================
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(string(case = "snake_case"))]
+enum Region {
+    UsEast,
+    EuWest,
+    #[convert(rename = "ap-south", alias = "apac", alias = "asia")]
+    ApSouth,
+    HTTPGateway,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(string)]
+enum Plain {
+    HTTPServer,
+    #[convert(rename = "db")]
+    Database,
+}
+
+fn main() {
+    assert_eq!(i32::from(Status::Unknown), 0);
+    assert_eq!(i32::from(Status::Active), 3);
+    assert_eq!(i32::from(Status::Suspended), 4);
+    assert_eq!(u8::from(Status::Deleted), 10);
+
+    assert_eq!(Status::try_from(4i32), Ok(Status::Suspended));
+    assert_eq!(Status::try_from(10u8), Ok(Status::Deleted));
+    assert!(Status::try_from(5i32).is_err());
+
+    let wire: WireAccount = Account {
+        id: 1,
+        status: Status::Active,
+    }
+    .into();
+    assert_eq!(wire, WireAccount { id: 1, status: 3 });
+
+    let account = CheckedAccount::try_from(WireAccount { id: 2, status: 0 }).unwrap();
+    assert_eq!(
+        account,
+        CheckedAccount {
+            id: 2,
+            status: Status::Unknown,
+        }
+    );
+
+    let account = CheckedAccount::try_from(WireAccount { id: 3, status: 10 }).unwrap();
+    assert_eq!(account.status, Status::Deleted);
+
+    let account = CheckedAccount::try_from(WireAccount { id: 4, status: 7 });
+    assert!(account.is_err());
+
+    assert_eq!(<&str>::from(Region::UsEast), "us_east");
+    assert_eq!(String::from(Region::ApSouth), "ap-south");
+    assert_eq!(Region::EuWest.to_string(), "eu_west");
+    assert_eq!(Region::try_from("eu_west"), Ok(Region::EuWest));
+    assert_eq!("apac".parse::<Region>(), Ok(Region::ApSouth));
+    assert_eq!("asia".parse::<Region>(), Ok(Region::ApSouth));
+    assert!("EuWest".parse::<Region>().is_err());
+    assert_eq!(Region::HTTPGateway.to_string(), "http_gateway");
+
+    assert_eq!(Plain::HTTPServer.to_string(), "HTTPServer");
+    assert_eq!("db".parse::<Plain>(), Ok(Plain::Database));
+}
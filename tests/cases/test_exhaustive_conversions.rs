@@ -0,0 +1,65 @@
+use derive_into::Convert;
+
+// =================== exhaustive ===================
+#[derive(Debug, PartialEq, Clone)]
+struct ApiUser {
+    id: u32,
+    login: String,
+    street: String,
+    city: String,
+    etag: String,
+    links: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+// Every field of `ApiUser` is mapped or ignored. A new field on `ApiUser` would stop
+// this from compiling.
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "ApiUser", exhaustive, ignore(etag, links)))]
+#[convert(from(path = "ApiUser", by_ref, exhaustive, ignore(etag, links)))]
+struct User {
+    id: u32,
+    #[convert(rename = "login")]
+    name: String,
+    #[convert(flatten(fields(street, city)))]
+    address: Address,
+    #[convert(default)]
+    verified: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiPoint(i32, i64);
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(try_from(path = "ApiPoint", exhaustive))]
+struct Point(i32, i32);
+
+fn main() {
+    let api_user = ApiUser {
+        id: 1,
+        login: "ada".to_string(),
+        street: "Main St".to_string(),
+        city: "London".to_string(),
+        etag: "abc".to_string(),
+        links: vec![],
+    };
+
+    let expected = User {
+        id: 1,
+        name: "ada".to_string(),
+        address: Address {
+            street: "Main St".to_string(),
+            city: "London".to_string(),
+        },
+        verified: false,
+    };
+    assert_eq!(User::from(&api_user), expected);
+    assert_eq!(User::from(api_user), expected);
+
+    assert_eq!(Point::try_from(ApiPoint(1, 2)), Ok(Point(1, 2)));
+}
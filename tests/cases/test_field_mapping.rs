@@ -0,0 +1,90 @@
+use derive_into::Convert;
+
+// =================== map and skip ===================
+#[derive(Convert, Debug, PartialEq, Clone, Default)]
+#[convert(into(
+    path = "PublicProfile",
+    map(user_name = "name", created = "created_at"),
+    skip(internal_id, password_hash)
+))]
+#[convert(from(
+    path = "PublicProfile",
+    default,
+    map(user_name = "name", created = "created_at"),
+    skip(internal_id, password_hash)
+))]
+#[convert(into(path = "AuditRow", map(user_name = "login")))]
+struct Profile {
+    internal_id: u64,
+    user_name: String,
+    password_hash: String,
+    created: u64,
+    // A field-level rename for one conversion wins over its `map`
+    #[convert(into(path = "AuditRow", rename = "mail"))]
+    email: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct PublicProfile {
+    name: String,
+    created_at: u64,
+    email: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct AuditRow {
+    internal_id: u64,
+    login: String,
+    password_hash: String,
+    created: u64,
+    mail: String,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "Level", map(Warning = "Warn"), skip(Trace)))]
+enum LogLevel {
+    Info,
+    Warning,
+    Trace,
+}
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Info,
+    Warn,
+}
+
+fn main() {
+    let profile = Profile {
+        internal_id: 9,
+        user_name: "ada".to_string(),
+        password_hash: "hash".to_string(),
+        created: 100,
+        email: "ada@example.com".to_string(),
+    };
+
+    let public: PublicProfile = profile.clone().into();
+    assert_eq!(
+        public,
+        PublicProfile {
+            name: "ada".to_string(),
+            created_at: 100,
+            email: "ada@example.com".to_string(),
+        }
+    );
+    assert_eq!(
+        Profile::from(public),
+        Profile {
+            internal_id: 0,
+            password_hash: String::new(),
+            ..profile.clone()
+        }
+    );
+
+    let row: AuditRow = profile.into();
+    assert_eq!(row.login, "ada");
+    assert_eq!(row.mail, "ada@example.com");
+
+    assert_eq!(LogLevel::from(Level::Warn), LogLevel::Warning);
+    assert_eq!(LogLevel::from(Level::Info), LogLevel::Info);
+}
@@ -0,0 +1,68 @@
+use derive_into::Convert;
+
+// =================== paths ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(paths = ["ApiV1", "ApiV2", "Internal"]))]
+#[convert(from(paths = ["ApiV1", "ApiV2"]))]
+struct Account {
+    id: u32,
+    // Each listed target can still be addressed on its own
+    #[convert(into(path = "ApiV2", rename = "display_name"))]
+    #[convert(from(path = "ApiV2", rename = "display_name"))]
+    name: String,
+    #[convert(into(path = "Internal", some))]
+    email: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiV1 {
+    id: u32,
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiV2 {
+    id: u32,
+    display_name: String,
+    email: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Internal {
+    id: u32,
+    name: String,
+    email: Option<String>,
+}
+
+fn main() {
+    let account = Account {
+        id: 1,
+        name: "ada".to_string(),
+        email: "ada@example.com".to_string(),
+    };
+
+    let v1: ApiV1 = account.clone().into();
+    assert_eq!(
+        v1,
+        ApiV1 {
+            id: 1,
+            name: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+        }
+    );
+    let v2: ApiV2 = account.clone().into();
+    assert_eq!(
+        v2,
+        ApiV2 {
+            id: 1,
+            display_name: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+        }
+    );
+    let internal: Internal = account.clone().into();
+    assert_eq!(internal.email, Some("ada@example.com".to_string()));
+
+    assert_eq!(Account::from(v1), account);
+    assert_eq!(Account::from(v2), account);
+}
@@ -0,0 +1,156 @@
+#![allow(non_snake_case, non_camel_case_types)]
+
+use derive_into::Convert;
+
+// =================== rename_all ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "UserDto", rename_all = "camelCase"))]
+#[convert(from(path = "UserDto", rename_all = "camelCase"))]
+#[convert(into(path = "UserRow"))]
+struct User {
+    user_id: u32,
+    display_name: String,
+    // An explicit rename wins over the convention
+    #[convert(into(path = "UserDto", rename = "mail"))]
+    #[convert(from(path = "UserDto", rename = "mail"))]
+    email_address: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserDto {
+    userId: u32,
+    displayName: String,
+    mail: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRow {
+    user_id: u32,
+    display_name: String,
+    email_address: String,
+}
+
+// A top-level convention applies to every conversion without its own
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(rename_all = "SCREAMING_SNAKE_CASE")]
+#[convert(try_from(path = "ProtoStatus"))]
+#[convert(into(path = "ProtoStatus"))]
+#[convert(into(path = "Status", rename_all = "PascalCase"))]
+enum AccountStatus {
+    Active,
+    PendingReview { days: u32 },
+    #[convert(rename = "UNKNOWN")]
+    Unspecified,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ProtoStatus {
+    ACTIVE,
+    PENDING_REVIEW { days: u32 },
+    UNKNOWN,
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Active,
+    PendingReview { days: u32 },
+    UNKNOWN,
+}
+
+// =================== prefixes and suffixes ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(both(path = "UserColumns", add_prefix = "usr_"))]
+struct Account {
+    name: String,
+    #[convert(rename = "created")]
+    created_at: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserColumns {
+    usr_name: String,
+    created: u64,
+}
+
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(into(
+    path = "ProtoPlan",
+    strip_suffix = "Plan",
+    rename_all = "SCREAMING_SNAKE_CASE",
+    add_prefix = "PLAN_"
+))]
+enum Plan {
+    FreePlan,
+    TeamPlan,
+    Enterprise,
+}
+
+#[derive(Debug, PartialEq)]
+enum ProtoPlan {
+    PLAN_FREE,
+    PLAN_TEAM,
+    PLAN_ENTERPRISE,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "Plan", strip_prefix = "Tier", add_suffix = "Plan"))]
+enum Tier {
+    TierFree,
+    TierTeam,
+    #[convert(rename = "Enterprise")]
+    Custom,
+}
+
+fn main() {
+    let user = User {
+        user_id: 1,
+        display_name: "Ada".to_string(),
+        email_address: "ada@example.com".to_string(),
+    };
+
+    let dto: UserDto = user.clone().into();
+    assert_eq!(
+        dto,
+        UserDto {
+            userId: 1,
+            displayName: "Ada".to_string(),
+            mail: "ada@example.com".to_string(),
+        }
+    );
+    assert_eq!(User::from(dto), user);
+
+    let row: UserRow = user.into();
+    assert_eq!(row.email_address, "ada@example.com");
+
+    let proto: ProtoStatus = AccountStatus::PendingReview { days: 2 }.into();
+    assert_eq!(proto, ProtoStatus::PENDING_REVIEW { days: 2 });
+    assert_eq!(
+        AccountStatus::try_from(ProtoStatus::UNKNOWN),
+        Ok(AccountStatus::Unspecified)
+    );
+    let status: Status = AccountStatus::Active.into();
+    assert_eq!(status, Status::Active);
+    let status: Status = AccountStatus::Unspecified.into();
+    assert_eq!(status, Status::UNKNOWN);
+
+    let account = Account {
+        name: "ada".to_string(),
+        created_at: 7,
+    };
+    let columns: UserColumns = account.clone().into();
+    assert_eq!(
+        columns,
+        UserColumns {
+            usr_name: "ada".to_string(),
+            created: 7,
+        }
+    );
+    assert_eq!(Account::from(columns), account);
+
+    let plan: ProtoPlan = Plan::FreePlan.into();
+    assert_eq!(plan, ProtoPlan::PLAN_FREE);
+    let plan: ProtoPlan = Plan::Enterprise.into();
+    assert_eq!(plan, ProtoPlan::PLAN_ENTERPRISE);
+    assert_eq!(Tier::from(Plan::TeamPlan), Tier::TierTeam);
+    assert_eq!(Tier::from(Plan::Enterprise), Tier::Custom);
+}
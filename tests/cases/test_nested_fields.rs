@@ -0,0 +1,154 @@
+use derive_into::Convert;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Email(String);
+
+impl From<String> for Email {
+    fn from(email: String) -> Self {
+        Email(email)
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> Self {
+        email.0
+    }
+}
+
+// =================== Flatten nested structs ===================
+#[derive(Debug, PartialEq, Clone)]
+struct Profile {
+    name: String,
+    email: String,
+}
+
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "UserRow"))]
+#[convert(from(path = "UserRow"))]
+#[convert(try_from(path = "CheckedUserRow"))]
+struct User {
+    id: u64,
+    #[convert(flatten(fields(name, email = "contact_email")))]
+    profile: Profile,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRow {
+    id: u64,
+    name: String,
+    contact_email: Email,
+}
+
+struct CheckedUserRow {
+    id: u64,
+    name: String,
+    contact_email: String,
+}
+
+// =================== Source field paths ===================
+struct Address {
+    city: String,
+    zip: String,
+}
+
+struct Meta {
+    created_at: u64,
+}
+
+struct Customer {
+    name: String,
+    address: Address,
+    billing: Option<Address>,
+    meta: Meta,
+    coordinates: (f64, f64),
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(from(path = "Customer"))]
+struct CustomerSummary {
+    name: String,
+    #[convert(source = "address.city")]
+    city: String,
+    #[convert(from(source = "meta.created_at"))]
+    created_at: u64,
+    #[convert(source = "coordinates.0")]
+    latitude: f64,
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(try_from(path = "Customer"))]
+struct BillingSummary {
+    name: String,
+    #[convert(source = "billing?.city")]
+    billing_city: String,
+    #[convert(source = "billing?.zip")]
+    billing_zip: String,
+}
+
+fn main() {
+    let user = User {
+        id: 1,
+        profile: Profile {
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+        },
+    };
+
+    let row: UserRow = user.clone().into();
+    assert_eq!(
+        row,
+        UserRow {
+            id: 1,
+            name: "Ada".to_string(),
+            contact_email: Email("ada@example.com".to_string()),
+        }
+    );
+    assert_eq!(User::from(row), user);
+
+    let checked = CheckedUserRow {
+        id: 1,
+        name: "Ada".to_string(),
+        contact_email: "ada@example.com".to_string(),
+    };
+    assert_eq!(User::try_from(checked), Ok(user));
+
+    let customer = || Customer {
+        name: "Grace".to_string(),
+        address: Address {
+            city: "Arlington".to_string(),
+            zip: "22201".to_string(),
+        },
+        billing: Some(Address {
+            city: "Washington".to_string(),
+            zip: "20001".to_string(),
+        }),
+        meta: Meta { created_at: 42 },
+        coordinates: (38.9, -77.1),
+    };
+
+    assert_eq!(
+        CustomerSummary::from(customer()),
+        CustomerSummary {
+            name: "Grace".to_string(),
+            city: "Arlington".to_string(),
+            created_at: 42,
+            latitude: 38.9,
+        }
+    );
+    assert_eq!(customer().address.zip, "22201");
+
+    assert_eq!(
+        BillingSummary::try_from(customer()),
+        Ok(BillingSummary {
+            name: "Grace".to_string(),
+            billing_city: "Washington".to_string(),
+            billing_zip: "20001".to_string(),
+        })
+    );
+
+    let without_billing = Customer {
+        billing: None,
+        ..customer()
+    };
+    assert!(BillingSummary::try_from(without_billing).is_err());
+}
@@ -0,0 +1,156 @@
+#![allow(non_snake_case, non_camel_case_types)]
+
+use derive_into::Convert;
+use serde::{Deserialize, Serialize};
+
+// =================== use_serde_names ===================
+#[derive(Convert, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[convert(into(path = "OrderDto", use_serde_names))]
+#[convert(from(path = "OrderDto", use_serde_names))]
+#[convert(into(path = "OrderRow"))]
+struct Order {
+    order_id: u32,
+    #[serde(rename = "total", default, skip_serializing_if = "Option::is_none")]
+    total_cents: Option<u64>,
+    // A rename of our own wins over the serde name
+    #[serde(rename = "notes")]
+    #[convert(into(path = "OrderDto", rename = "comment"))]
+    #[convert(from(path = "OrderDto", rename = "comment"))]
+    note: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct OrderDto {
+    orderId: u32,
+    total: Option<u64>,
+    comment: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct OrderRow {
+    order_id: u32,
+    total_cents: Option<u64>,
+    note: String,
+}
+
+#[derive(Convert, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+// An explicit convention wins over serde's
+#[convert(into(path = "Shipping", use_serde_names, rename_all = "PascalCase"))]
+#[convert(into(path = "ProtoShipping", use_serde_names))]
+enum Delivery {
+    Standard,
+    #[serde(rename = "EXPRESS_AIR")]
+    Express {
+        #[serde(rename = "hrs")]
+        hours: u8,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum Shipping {
+    Standard,
+    EXPRESS_AIR { hrs: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+enum ProtoShipping {
+    STANDARD,
+    EXPRESS_AIR { hrs: u8 },
+}
+
+// Serde writes fields and variants after rules of its own, which keep field words apart
+// and start a variant word at every uppercase letter
+#[derive(Convert, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+#[convert(into(path = "UserRow", use_serde_names))]
+struct UserUpper {
+    user_name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRow {
+    USER_NAME: String,
+}
+
+#[derive(Convert, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[convert(into(path = "UserRecord", use_serde_names))]
+struct UserLower {
+    user_name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRecord {
+    user_name: String,
+}
+
+#[derive(Convert, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[convert(into(path = "ServerKind", use_serde_names))]
+enum Server {
+    HTTPServer,
+    FileStore,
+}
+
+#[derive(Debug, PartialEq)]
+enum ServerKind {
+    h_t_t_p_server,
+    file_store,
+}
+
+fn main() {
+    let order = Order {
+        order_id: 3,
+        total_cents: Some(990),
+        note: "fragile".to_string(),
+    };
+
+    let dto: OrderDto = order.clone().into();
+    assert_eq!(
+        dto,
+        OrderDto {
+            orderId: 3,
+            total: Some(990),
+            comment: "fragile".to_string(),
+        }
+    );
+    assert_eq!(Order::from(dto), order);
+
+    let row: OrderRow = order.into();
+    assert_eq!(row.total_cents, Some(990));
+
+    let shipping: Shipping = Delivery::Standard.into();
+    assert_eq!(shipping, Shipping::Standard);
+    let shipping: ProtoShipping = Delivery::Standard.into();
+    assert_eq!(shipping, ProtoShipping::STANDARD);
+    let shipping: ProtoShipping = Delivery::Express { hours: 4 }.into();
+    assert_eq!(shipping, ProtoShipping::EXPRESS_AIR { hrs: 4 });
+
+    let row: UserRow = UserUpper {
+        user_name: "ada".to_string(),
+    }
+    .into();
+    assert_eq!(
+        row,
+        UserRow {
+            USER_NAME: "ada".to_string(),
+        }
+    );
+    let record: UserRecord = UserLower {
+        user_name: "ada".to_string(),
+    }
+    .into();
+    assert_eq!(
+        record,
+        UserRecord {
+            user_name: "ada".to_string(),
+        }
+    );
+
+    let kind: ServerKind = Server::HTTPServer.into();
+    assert_eq!(kind, ServerKind::h_t_t_p_server);
+    let kind: ServerKind = Server::FileStore.into();
+    assert_eq!(kind, ServerKind::file_store);
+}
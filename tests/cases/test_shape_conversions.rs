@@ -0,0 +1,186 @@
+use derive_into::Convert;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Meters(f64);
+
+impl From<f64> for Meters {
+    fn from(m: f64) -> Self {
+        Meters(m)
+    }
+}
+
+impl From<Meters> for f64 {
+    fn from(m: Meters) -> Self {
+        m.0
+    }
+}
+
+// =================== Positional <-> named structs ===================
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(into(path = "NamedPoint"))]
+#[convert(from(path = "NamedPoint"))]
+struct Point(#[convert(name = "x")] f64, #[convert(name = "y")] f64);
+
+#[derive(Debug, PartialEq)]
+struct NamedPoint {
+    x: Meters,
+    y: Meters,
+}
+
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(into(path = "PositionalSize"))]
+#[convert(try_from(path = "PositionalSize"))]
+struct Size {
+    #[convert(index = 1)]
+    height: f64,
+    #[convert(index = 0)]
+    width: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct PositionalSize(Meters, Meters);
+
+// =================== Tuples and arrays ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(tuple))]
+#[convert(from(tuple))]
+struct UserRow {
+    id: i64,
+    name: String,
+    active: bool,
+}
+
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(into(array))]
+#[convert(from(array))]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Convert, Debug, PartialEq, Clone, Copy)]
+#[convert(into(array))]
+#[convert(try_from(array))]
+struct Rgb(u8, u8, u8);
+
+// =================== Default filling for positional targets ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "Rgba", default, arity = 4, fill(index = 3, value = "255")))]
+#[convert(into(path = "Padded", default, arity = 3))]
+#[convert(from(path = "Rgba", default))]
+struct Rgb8(u8, u8, #[convert(from(skip))] u8);
+
+#[derive(Debug, PartialEq)]
+struct Rgba(u8, u8, u8, u8);
+
+#[derive(Debug, PartialEq)]
+struct Padded(u8, u8, u8);
+
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "WideShape", default))]
+enum Shape {
+    #[convert(arity = 3, fill(index = 2, value = "1.0"))]
+    Line(f64, f64),
+    #[convert(arity = 2)]
+    Dot(f64),
+    Empty,
+}
+
+#[derive(Debug, PartialEq)]
+enum WideShape {
+    Line(f64, f64, f64),
+    Dot(f64, u8),
+    Empty,
+}
+
+// =================== Unit structs ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "Ack"))]
+#[convert(from(path = "Ack"))]
+#[convert(into(path = "State", variant = "Idle"))]
+#[convert(try_from(path = "State", variant = "Idle"))]
+#[convert(into(path = "Settings", default))]
+struct Idle;
+
+#[derive(Debug, PartialEq)]
+struct Ack;
+
+#[derive(Debug, PartialEq)]
+enum State {
+    Idle,
+    Running,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Settings {
+    retries: u8,
+    verbose: bool,
+}
+
+fn main() {
+    let point = Point(1.0, 2.0);
+    let named: NamedPoint = point.into();
+    assert_eq!(
+        named,
+        NamedPoint {
+            x: Meters(1.0),
+            y: Meters(2.0),
+        }
+    );
+    assert_eq!(Point::from(named), point);
+
+    let size = Size {
+        width: 3.0,
+        height: 4.0,
+    };
+    let positional: PositionalSize = size.into();
+    assert_eq!(positional, PositionalSize(Meters(3.0), Meters(4.0)));
+    assert_eq!(Size::try_from(positional), Ok(size));
+
+    let row = UserRow {
+        id: 7,
+        name: "ada".to_string(),
+        active: true,
+    };
+    let tuple: (i64, String, bool) = row.clone().into();
+    assert_eq!(tuple, (7, "ada".to_string(), true));
+    assert_eq!(UserRow::from(tuple), row);
+
+    let v = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let array: [f32; 3] = v.into();
+    assert_eq!(array, [1.0, 2.0, 3.0]);
+    assert_eq!(Vec3::from(array), v);
+
+    let rgb = Rgb(255, 128, 0);
+    let array: [u8; 3] = rgb.into();
+    assert_eq!(array, [255, 128, 0]);
+    assert_eq!(Rgb::try_from(array), Ok(rgb));
+
+    let rgba: Rgba = Rgb8(1, 2, 3).into();
+    assert_eq!(rgba, Rgba(1, 2, 3, 255));
+    let padded: Padded = Rgb8(1, 2, 3).into();
+    assert_eq!(padded, Padded(1, 2, 3));
+    assert_eq!(Rgb8::from(Rgba(1, 2, 3, 4)), Rgb8(1, 2, 0));
+
+    let line: WideShape = Shape::Line(0.5, 2.0).into();
+    assert_eq!(line, WideShape::Line(0.5, 2.0, 1.0));
+    let dot: WideShape = Shape::Dot(3.0).into();
+    assert_eq!(dot, WideShape::Dot(3.0, 0));
+    let empty: WideShape = Shape::Empty.into();
+    assert_eq!(empty, WideShape::Empty);
+
+    let ack: Ack = Idle.into();
+    assert_eq!(ack, Ack);
+    assert_eq!(Idle::from(Ack), Idle);
+    let state: State = Idle.into();
+    assert_eq!(state, State::Idle);
+    assert_eq!(Idle::try_from(State::Idle), Ok(Idle));
+    assert!(Idle::try_from(State::Running).is_err());
+    let settings: Settings = Idle.into();
+    assert_eq!(settings, Settings::default());
+}
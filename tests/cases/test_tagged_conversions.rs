@@ -0,0 +1,79 @@
+use derive_into::Convert;
+
+// =================== Enum <-> tagged struct ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "TransactionRecord", tag = "kind"))]
+#[convert(try_from(path = "TransactionRecord", tag = "kind"))]
+enum Transaction {
+    Deposit {
+        #[convert(some)]
+        amount: u64,
+    },
+    #[convert(rename = "transfer")]
+    Transfer {
+        #[convert(some)]
+        amount: u64,
+        #[convert(some, rename = "target")]
+        to: String,
+    },
+    #[convert(fields = ["target"])]
+    Close(Option<String>),
+    Freeze,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct TransactionRecord {
+    kind: String,
+    amount: Option<u64>,
+    target: Option<String>,
+}
+
+fn main() {
+    let record: TransactionRecord = Transaction::Deposit { amount: 5 }.into();
+    assert_eq!(
+        record,
+        TransactionRecord {
+            kind: "Deposit".to_string(),
+            amount: Some(5),
+            target: None,
+        }
+    );
+
+    let transfer = Transaction::Transfer {
+        amount: 7,
+        to: "bob".to_string(),
+    };
+    let record: TransactionRecord = transfer.clone().into();
+    assert_eq!(
+        record,
+        TransactionRecord {
+            kind: "transfer".to_string(),
+            amount: Some(7),
+            target: Some("bob".to_string()),
+        }
+    );
+    assert_eq!(Transaction::try_from(record), Ok(transfer));
+
+    let close = Transaction::Close(Some("savings".to_string()));
+    let record: TransactionRecord = close.clone().into();
+    assert_eq!(record.kind, "Close");
+    assert_eq!(Transaction::try_from(record), Ok(close));
+
+    let record: TransactionRecord = Transaction::Freeze.into();
+    assert_eq!(record.kind, "Freeze");
+    assert_eq!(record.amount, None);
+    assert_eq!(Transaction::try_from(record), Ok(Transaction::Freeze));
+
+    // A missing amount can't become a Deposit
+    let record = TransactionRecord {
+        kind: "Deposit".to_string(),
+        ..Default::default()
+    };
+    assert!(Transaction::try_from(record).is_err());
+
+    let record = TransactionRecord {
+        kind: "Withdraw".to_string(),
+        ..Default::default()
+    };
+    assert!(Transaction::try_from(record).is_err());
+}
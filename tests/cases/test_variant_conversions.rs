@@ -0,0 +1,322 @@
+use derive_into::Convert;
+
+#[derive(Debug, PartialEq)]
+struct Meters(u64);
+
+impl From<u64> for Meters {
+    fn from(m: u64) -> Self {
+        Meters(m)
+    }
+}
+
+// =================== Variant-level with_func ===================
+fn shape_from_square(side: u64) -> TargetShape {
+    TargetShape::Rect {
+        width: Meters(side),
+        height: Meters(side),
+    }
+}
+
+fn shape_from_label(label: &String, size: &u64) -> TargetShape {
+    TargetShape::Labeled(format!("{label}:{size}"))
+}
+
+fn shape_from_number(number: u64) -> TargetShape {
+    TargetShape::Labeled(number.to_string())
+}
+
+fn shape_try_from_label(label: String) -> Result<SourceShape, String> {
+    label
+        .parse()
+        .map(SourceShape::Parsed)
+        .map_err(|_| format!("{label} is not a number"))
+}
+
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "TargetShape"))]
+#[convert(try_from(path = "TargetShape"))]
+enum SourceShape {
+    #[convert(into(with_func = shape_from_square))]
+    #[convert(try_from(skip))]
+    Square(u64),
+    Rect {
+        width: u64,
+        height: u64,
+    },
+    #[convert(into(with_func = shape_from_label, by_ref))]
+    #[convert(try_from(skip))]
+    Labeled { label: String, size: u64 },
+    #[convert(into(with_func = shape_from_number))]
+    #[convert(try_from(rename = "Labeled", with_func = shape_try_from_label))]
+    Parsed(u64),
+}
+
+#[derive(Debug, PartialEq)]
+enum TargetShape {
+    Rect { width: Meters, height: Meters },
+    Labeled(String),
+}
+
+impl From<Meters> for u64 {
+    fn from(m: Meters) -> Self {
+        m.0
+    }
+}
+
+// =================== Field-level with_func in variants ===================
+fn describe(width: &u64, height: &u64, label: &String) -> String {
+    format!("{label} ({width}x{height})")
+}
+
+#[derive(Convert)]
+#[convert(into(path = "TargetMeasured"))]
+enum SourceMeasured {
+    Rect {
+        width: u64,
+        height: u64,
+        #[convert(with_func = describe)]
+        label: String,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum TargetMeasured {
+    Rect {
+        width: Meters,
+        height: Meters,
+        label: String,
+    },
+}
+
+// A conversion function still sees the fields declared before it, which the other
+// initializers move
+fn length_of(name: &String, _length: &usize) -> usize {
+    name.len()
+}
+
+#[derive(Convert)]
+#[convert(into(path = "TargetNamed"))]
+enum SourceNamed {
+    File {
+        name: String,
+        #[convert(with_func = length_of)]
+        length: usize,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum TargetNamed {
+    File { name: String, length: usize },
+}
+
+// `default` fields aren't read from the source, so conversion functions don't get them
+fn double(count: &u32) -> u32 {
+    count * 2
+}
+
+#[derive(Convert)]
+#[convert(into(path = "TargetCounted"))]
+enum SourceCounted {
+    Batch {
+        #[convert(default)]
+        extra: u32,
+        #[convert(with_func = double)]
+        count: u32,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum TargetCounted {
+    Batch { extra: u32, count: u32 },
+}
+
+// =================== Tuple <-> struct variant reshaping ===================
+#[derive(Convert, Debug, PartialEq, Clone)]
+#[convert(into(path = "NamedInput"))]
+#[convert(from(path = "NamedInput"))]
+enum PositionalInput {
+    #[convert(fields = ["x", "y"])]
+    Click(u64, u64),
+    Scroll(#[convert(name = "delta")] i32),
+    Key {
+        #[convert(index = 1)]
+        shift: bool,
+        #[convert(index = 0)]
+        code: u32,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum NamedInput {
+    Click { x: Meters, y: Meters },
+    Scroll { delta: i32 },
+    Key(u32, bool),
+}
+
+// =================== Unit <-> payload variants ===================
+#[derive(Convert, Debug, PartialEq)]
+#[convert(into(path = "Summary"))]
+#[convert(from(path = "Summary"))]
+enum Detailed {
+    #[convert(into(discard_fields))]
+    #[convert(from(default_fields(retries = "3")))]
+    Failed { reason: String, retries: u8 },
+    #[convert(into(discard_fields))]
+    #[convert(from(default_fields))]
+    Pending(u64),
+    #[convert(into(default_fields(progress, note = "String::from(\"done\")")))]
+    #[convert(from(discard_fields))]
+    Finished,
+    // Positions fill a tuple variant
+    #[convert(into(default_fields(0, 1 = "String::from(\"queued\")")))]
+    #[convert(from(discard_fields))]
+    Queued,
+    #[convert(into(discard_fields))]
+    #[convert(from(default_fields(1 = 9)))]
+    Retrying(String, u8),
+}
+
+#[derive(Debug, PartialEq)]
+enum Summary {
+    Failed,
+    Pending,
+    Finished { progress: u8, note: String },
+    Queued(u8, String),
+    Retrying,
+}
+
+fn main() {
+    let target: TargetShape = SourceShape::Square(3).into();
+    assert_eq!(
+        target,
+        TargetShape::Rect {
+            width: Meters(3),
+            height: Meters(3),
+        }
+    );
+
+    let target: TargetShape = SourceShape::Labeled {
+        label: "box".to_string(),
+        size: 2,
+    }
+    .into();
+    assert_eq!(target, TargetShape::Labeled("box:2".to_string()));
+
+    let source = SourceShape::try_from(TargetShape::Labeled("7".to_string())).unwrap();
+    assert_eq!(source, SourceShape::Parsed(7));
+    assert!(SourceShape::try_from(TargetShape::Labeled("seven".to_string())).is_err());
+    let target: TargetShape = source.into();
+    assert_eq!(target, TargetShape::Labeled("7".to_string()));
+
+    let source = SourceShape::try_from(TargetShape::Rect {
+        width: Meters(1),
+        height: Meters(2),
+    })
+    .unwrap();
+    assert_eq!(
+        source,
+        SourceShape::Rect {
+            width: 1,
+            height: 2
+        }
+    );
+
+    let target: TargetMeasured = SourceMeasured::Rect {
+        width: 2,
+        height: 5,
+        label: "door".to_string(),
+    }
+    .into();
+    assert_eq!(
+        target,
+        TargetMeasured::Rect {
+            width: Meters(2),
+            height: Meters(5),
+            label: "door (2x5)".to_string(),
+        }
+    );
+
+    let target: TargetNamed = SourceNamed::File {
+        name: "notes.txt".to_string(),
+        length: 0,
+    }
+    .into();
+    assert_eq!(
+        target,
+        TargetNamed::File {
+            name: "notes.txt".to_string(),
+            length: 9,
+        }
+    );
+
+    let target: TargetCounted = SourceCounted::Batch { extra: 7, count: 4 }.into();
+    assert_eq!(target, TargetCounted::Batch { extra: 0, count: 8 });
+
+    let input = PositionalInput::Click(3, 4);
+    let named: NamedInput = input.clone().into();
+    assert_eq!(
+        named,
+        NamedInput::Click {
+            x: Meters(3),
+            y: Meters(4),
+        }
+    );
+    assert_eq!(PositionalInput::from(named), input);
+
+    let named: NamedInput = PositionalInput::Scroll(-2).into();
+    assert_eq!(named, NamedInput::Scroll { delta: -2 });
+
+    let input = PositionalInput::Key {
+        shift: true,
+        code: 65,
+    };
+    let named: NamedInput = input.clone().into();
+    assert_eq!(named, NamedInput::Key(65, true));
+    assert_eq!(PositionalInput::from(named), input);
+
+    let summary: Summary = Detailed::Failed {
+        reason: "timeout".to_string(),
+        retries: 1,
+    }
+    .into();
+    assert_eq!(summary, Summary::Failed);
+    assert_eq!(Summary::from(Detailed::Pending(10)), Summary::Pending);
+    assert_eq!(
+        Summary::from(Detailed::Finished),
+        Summary::Finished {
+            progress: 0,
+            note: "done".to_string(),
+        }
+    );
+
+    assert_eq!(
+        Detailed::from(Summary::Failed),
+        Detailed::Failed {
+            reason: String::new(),
+            retries: 3,
+        }
+    );
+    assert_eq!(Detailed::from(Summary::Pending), Detailed::Pending(0));
+    let finished = Summary::Finished {
+        progress: 100,
+        note: "ok".to_string(),
+    };
+    assert_eq!(Detailed::from(finished), Detailed::Finished);
+
+    assert_eq!(
+        Summary::from(Detailed::Queued),
+        Summary::Queued(0, "queued".to_string())
+    );
+    assert_eq!(
+        Detailed::from(Summary::Queued(3, "later".to_string())),
+        Detailed::Queued
+    );
+    assert_eq!(
+        Summary::from(Detailed::Retrying("busy".to_string(), 2)),
+        Summary::Retrying
+    );
+    assert_eq!(
+        Detailed::from(Summary::Retrying),
+        Detailed::Retrying(String::new(), 9)
+    );
+}